@@ -0,0 +1,52 @@
+//! Benchmark the rendering of the help of a command with 300 arguments.
+//!
+//! Run with `cargo bench`.
+
+use {
+    clap::{Arg, ArgAction, Command},
+    clap_help::{Printer, TestOptions},
+    criterion::{criterion_group, criterion_main, Criterion},
+};
+
+const ARG_COUNT: usize = 300;
+
+fn command() -> Command {
+    let mut cmd = Command::new("many-options")
+        .about("A command with a lot of arguments")
+        .disable_help_flag(true);
+    for i in 0..ARG_COUNT {
+        // clap needs static names without its `string` feature
+        let name: &'static str = Box::leak(format!("option-{i}").into_boxed_str());
+        let arg = Arg::new(name).long(name);
+        let arg = match i % 3 {
+            0 => arg
+                .value_name("WIDTH")
+                .default_value("80")
+                .help("Width of the **table**, in columns"),
+            1 => arg
+                .value_names(["FROM", "TO"])
+                .num_args(2)
+                .value_parser(["fast", "precise", "both"])
+                .help("Mapping of a `value` to another one"),
+            _ => arg
+                .action(ArgAction::SetTrue)
+                .help("Enable a feature, see {--option-0}"),
+        };
+        cmd = cmd.arg(arg);
+    }
+    cmd
+}
+
+fn render_many_options(c: &mut Criterion) {
+    let cmd = command();
+    c.bench_function("render 300 arguments", |b| {
+        b.iter_batched(
+            || Printer::new(cmd.clone()),
+            |printer| printer.render_for_test(120, TestOptions::default()),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, render_many_options);
+criterion_main!(benches);
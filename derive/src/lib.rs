@@ -0,0 +1,119 @@
+/*!
+
+Derive macro for [clap-help](https://docs.rs/clap-help).
+
+You shouldn't depend on this crate directly but enable the `derive`
+feature of clap-help.
+
+*/
+
+use {
+    proc_macro::TokenStream,
+    proc_macro2::TokenStream as TokenStream2,
+    quote::{quote, quote_spanned},
+    syn::{parse_macro_input, DeriveInput, LitBool, LitInt, LitStr},
+};
+
+/// Generate a `print_rich_help()` associated function printing the
+/// help of a clap derived struct with clap-help.
+///
+/// Supported settings, in a `#[clap_help(...)]` attribute:
+///
+/// - `theme = "sunset"`: the name of a style preset
+/// - `options = "list"`: the layout of the options section, one of
+///   `table`, `merged`, `list`, `verbose`, `two-columns` and `three-columns`
+/// - `max_width = 100`: a maximal width for the help
+/// - `full_width = true`: use the whole terminal width
+/// - `introduction = "..."`: a markdown introduction
+/// - `without = "author"`: remove a section (can be repeated)
+#[proc_macro_derive(HelpPrinter, attributes(clap_help))]
+pub fn derive_help_printer(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut settings = Vec::new();
+    // the names are checked at compile time against the lists of clap-help
+    let mut checks = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("clap_help"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("theme") {
+                let lit: LitStr = meta.value()?.parse()?;
+                let message = format!("unknown style preset {:?}", lit.value())
+                    .replace('{', "{{")
+                    .replace('}', "}}");
+                checks.push(quote_spanned!(lit.span()=>
+                    const _: () = assert!(
+                        ::clap_help::derive_support::is_style_preset(#lit),
+                        #message,
+                    );
+                ));
+                settings.push(quote!(
+                    printer = printer.with_preset(
+                        ::clap_help::StylePreset::find(#lit).expect("checked at compile time"),
+                    );
+                ));
+            } else if meta.path.is_ident("options") {
+                let lit: LitStr = meta.value()?.parse()?;
+                let message = format!("unknown options layout {:?}", lit.value())
+                    .replace('{', "{{")
+                    .replace('}', "}}");
+                checks.push(quote_spanned!(lit.span()=>
+                    const _: () = assert!(
+                        ::clap_help::derive_support::is_options_layout(#lit),
+                        #message,
+                    );
+                ));
+                settings.push(quote!(
+                    printer = printer.with(
+                        "options",
+                        ::clap_help::options_layout(#lit).expect("checked at compile time"),
+                    );
+                ));
+            } else if meta.path.is_ident("max_width") {
+                let lit: LitInt = meta.value()?.parse()?;
+                let max_width: usize = lit.base10_parse()?;
+                settings.push(quote!(printer = printer.with_max_width(#max_width);));
+            } else if meta.path.is_ident("full_width") {
+                let lit: LitBool = meta.value()?.parse()?;
+                settings.push(quote!(printer.full_width = #lit;));
+            } else if meta.path.is_ident("introduction") {
+                let lit: LitStr = meta.value()?.parse()?;
+                settings.push(quote!(printer = printer.with("introduction", #lit);));
+            } else if meta.path.is_ident("without") {
+                let lit: LitStr = meta.value()?.parse()?;
+                settings.push(quote!(printer = printer.without(#lit);));
+            } else {
+                return Err(meta.error(
+                    "unknown clap_help setting, expected theme, options, max_width, \
+                     full_width, introduction, or without",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #(#checks)*
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Print the help with clap-help
+            pub fn print_rich_help() {
+                #[allow(unused_mut)]
+                let mut printer = ::clap_help::Printer::new(
+                    <Self as ::clap::CommandFactory>::command()
+                );
+                #(#settings)*
+                printer.print_help();
+            }
+        }
+    })
+}
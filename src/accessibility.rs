@@ -0,0 +1,11 @@
+/// How the help conveys the nature of the elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AccessibilityMode {
+    /// Values, like defaults and possible values, are distinguished
+    /// by their style
+    #[default]
+    Standard,
+    /// Nothing is conveyed only by colors or styles: required options
+    /// are marked with `${required}`, and values are quoted
+    TextMarkers,
+}
@@ -0,0 +1,16 @@
+use clap::Arg;
+
+/// Return the name under which the argument is known by the user,
+/// eg `--format`, `-f`, or `<FILE>` for a positional argument
+pub(crate) fn arg_name(arg: &Arg) -> String {
+    if let Some(long) = arg.get_long() {
+        format!("--{long}")
+    } else if let Some(short) = arg.get_short() {
+        format!("-{short}")
+    } else {
+        match arg.get_value_names().and_then(|names| names.first()) {
+            Some(name) => format!("<{name}>"),
+            None => format!("<{}>", arg.get_id()),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use {
+    crate::detection::{terminal_background, Detection},
+    termimad::{crossterm::style::Color, CompoundStyle, MadSkin},
+};
+
+pub use terminal_light::Rgb;
+
+/// The minimal contrast ratio of normal text for the WCAG level AA
+pub const WCAG_AA: f32 = 4.5;
+
+/// The minimal contrast ratio of normal text for the WCAG level AAA
+pub const WCAG_AAA: f32 = 7.0;
+
+/// The contrast of a styled element of a skin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementContrast {
+    /// The element of the skin, eg `"italic"` or `"header-1"`
+    pub element: &'static str,
+    pub foreground: Rgb,
+    pub background: Rgb,
+    /// The WCAG contrast ratio, from 1 to 21
+    pub ratio: f32,
+}
+
+impl ElementContrast {
+    /// Tell whether the element reaches the WCAG level AA
+    pub fn is_readable(&self) -> bool {
+        self.ratio >= WCAG_AA
+    }
+}
+
+/// The contrasts of the styled elements of a skin on a background.
+///
+/// Elements without foreground color are supposed written in white
+/// on a dark background and in black on a light one.
+///
+/// ```
+/// use clap_help::{Rgb, SkinAudit, StylePreset};
+///
+/// let audit = StylePreset::find("high-contrast")
+///     .unwrap()
+///     .contrast_report(Rgb::new(0, 0, 0));
+/// for element in audit.unreadable() {
+///     eprintln!("{} is hard to read ({:.1}:1)", element.element, element.ratio);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkinAudit {
+    pub background: Rgb,
+    pub elements: Vec<ElementContrast>,
+}
+
+impl SkinAudit {
+    /// Audit the skin on the background of the terminal, or
+    /// on a black one when it can't be determined.
+    ///
+    /// The terminal is queried like by [crate::Printer::new], so not
+    /// with the `no-detect` feature, nor when stdout isn't a terminal.
+    pub fn check(skin: &MadSkin) -> Self {
+        Self::check_on(skin, terminal_background(Detection::default()))
+    }
+
+    /// Audit the skin on the given background
+    pub fn check_on(skin: &MadSkin, background: Rgb) -> Self {
+        const HEADERS: [&str; 8] = [
+            "header-1", "header-2", "header-3", "header-4", "header-5", "header-6", "header-7",
+            "header-8",
+        ];
+        let text = &skin.paragraph.compound_style;
+        let default_fg = if background.luma() < 0.5 {
+            Rgb::new(255, 255, 255)
+        } else {
+            Rgb::new(0, 0, 0)
+        };
+        let contrast = |element, style: &CompoundStyle| {
+            let rgb = |color: Option<Color>| color.map(|c| terminal_light::Color::from(c).rgb());
+            let foreground = rgb(style.get_fg())
+                .or_else(|| rgb(text.get_fg()))
+                .unwrap_or(default_fg);
+            let background = rgb(style.get_bg())
+                .or_else(|| rgb(text.get_bg()))
+                .unwrap_or(background);
+            ElementContrast {
+                element,
+                foreground,
+                background,
+                ratio: contrast_ratio(foreground, background),
+            }
+        };
+        let mut elements = vec![
+            contrast("paragraph", text),
+            contrast("bold", &skin.bold),
+            contrast("italic", &skin.italic),
+            contrast("strikeout", &skin.strikeout),
+            contrast("inline-code", &skin.inline_code),
+            contrast("code-block", &skin.code_block.compound_style),
+        ];
+        for (element, header) in HEADERS.iter().zip(&skin.headers) {
+            elements.push(contrast(element, &header.compound_style));
+        }
+        elements.push(contrast("table-border", &skin.table.compound_style));
+        let mut bullet = CompoundStyle::default();
+        if let Some(fg) = skin.bullet.get_fg() {
+            bullet.set_fg(fg);
+        }
+        if let Some(bg) = skin.bullet.get_bg() {
+            bullet.set_bg(bg);
+        }
+        elements.push(contrast("bullet", &bullet));
+        Self {
+            background,
+            elements,
+        }
+    }
+
+    /// The elements below the WCAG level AA
+    pub fn unreadable(&self) -> impl Iterator<Item = &ElementContrast> {
+        self.elements
+            .iter()
+            .filter(|element| !element.is_readable())
+    }
+}
+
+/// The WCAG relative luminance of a color
+fn relative_luminance(color: Rgb) -> f32 {
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+}
+
+/// The WCAG contrast ratio of two colors, from 1 (same luminance)
+/// to 21 (black and white)
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
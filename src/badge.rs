@@ -0,0 +1,56 @@
+use {
+    crate::line_style::{index_marker, restyle_marked, BADGE_MARK},
+    termimad::crossterm::style::{Color, SetForegroundColor},
+};
+
+/// A small label displayed after the name of an option, eg
+/// "experimental", see [crate::Printer::badge]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Badge {
+    pub text: String,
+    pub color: Option<Color>,
+    /// Explanation of the badge, given in the legend line
+    pub description: Option<String>,
+}
+
+impl Badge {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+            description: None,
+        }
+    }
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+    /// Return the badge as markdown, as inline code whose text is
+    /// surrounded with invisible markers when it's to be colored
+    pub(crate) fn md(&self, idx: usize) -> String {
+        let text = self.text.replace('`', "'");
+        match self.color {
+            Some(_) => format!(" `{}{text}{BADGE_MARK}`", index_marker(BADGE_MARK, idx)),
+            None => format!(" `{text}`"),
+        }
+    }
+}
+
+/// Color the texts of the badges found with their markers in the
+/// rendered text, the index of the marker being the one of the badge.
+///
+/// Badges which were wrapped aren't colored.
+pub(crate) fn color_badges(rendered: &str, badges: &[&Badge]) -> String {
+    restyle_marked(rendered, BADGE_MARK, |idx, text| {
+        let color = badges.get(idx).and_then(|badge| badge.color)?;
+        Some(format!(
+            "{}{text}{}",
+            SetForegroundColor(color),
+            SetForegroundColor(Color::Reset),
+        ))
+    })
+}
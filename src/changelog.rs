@@ -0,0 +1,59 @@
+use std::cmp::Ordering;
+
+/// A flag, an option or a subcommand added in a version of the
+/// application, to be listed by [crate::Printer::with_changelog_since]
+///
+/// ```
+/// use clap_help::ChangelogEntry;
+///
+/// let entries = [
+///     ChangelogEntry::new("2.3", "--watch"),
+///     ChangelogEntry::new("2.4", "export").with_description("Export the tree as JSON"),
+/// ];
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChangelogEntry {
+    /// The version which introduced the item, eg `2.3`
+    pub version: String,
+    /// The name of the flag (eg `--watch`) or of the subcommand
+    pub name: String,
+    /// The description of the item, if not the help of the
+    /// option or subcommand of the same name
+    pub description: Option<String>,
+}
+
+impl ChangelogEntry {
+    pub fn new(version: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            name: name.into(),
+            description: None,
+        }
+    }
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// Compare two versions like `2.3`, `v2.10.1` or `3.0.0-beta`, component
+/// by component, only the leading digits of each component being used
+pub(crate) fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn components(version: &str) -> impl Iterator<Item = u64> + '_ {
+        version.trim_start_matches('v').split('.').map(|c| {
+            let digits = c.len() - c.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            c[..digits].parse().unwrap_or(0)
+        })
+    }
+    let mut a = components(a);
+    let mut b = components(b);
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (a, b) => match a.unwrap_or(0).cmp(&b.unwrap_or(0)) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            },
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Copy of example commands to the clipboard, so that the user can
+//! paste them in their shell.
+
+use {
+    crate::{
+        picker::{pick, Choice},
+        Printer,
+    },
+    std::io::{self, Write},
+    termimad::{crossterm::tty::IsTty, MadSkin},
+};
+
+const BASE64_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_CHARS[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Copy the text to the system clipboard with the OSC 52 escape
+/// sequence, which most terminal emulators (and tmux with its
+/// `set-clipboard` option) understand.
+///
+/// Return false when the output isn't a terminal.
+pub fn copy_to_clipboard(text: &str) -> bool {
+    let mut stdout = io::stdout();
+    if !stdout.is_tty() {
+        return false;
+    }
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes())).is_ok() && stdout.flush().is_ok()
+}
+
+/// Let the user pick one of the examples, given as (title, command),
+/// in a list filtered as they type, with the skin, then copy its
+/// command to the clipboard and print it, without style, so that it
+/// can also be copied manually.
+///
+/// Return the command, or `None` when the user quit the list.
+pub fn pick_example_with_skin(examples: &[(&str, &str)], skin: &MadSkin) -> Option<String> {
+    let choices: Vec<Choice> = examples
+        .iter()
+        .map(|(title, command)| Choice {
+            name: command.to_string(),
+            about: title.to_string(),
+        })
+        .collect();
+    let idx = pick("Examples", &choices, skin)?;
+    let command = choices.into_iter().nth(idx)?.name;
+    if copy_to_clipboard(&command) {
+        let _ = writeln!(io::stderr(), "copied to the clipboard:");
+    }
+    let _ = writeln!(io::stdout(), "{command}");
+    Some(command)
+}
+
+/// Let the user pick one of the examples, given as (title, command),
+/// with a skin fitting the terminal's theme, then copy its command
+/// to the clipboard and print it.
+///
+/// ```no_run
+/// static EXAMPLES: &[(&str, &str)] = &[
+///     ("Compute for a height of 37", "withex -h 37"),
+///     ("Maximum precision", "withex -h 37 -w 28 --strategy precise"),
+/// ];
+/// clap_help::clipboard::pick_example(EXAMPLES);
+/// ```
+pub fn pick_example(examples: &[(&str, &str)]) -> Option<String> {
+    pick_example_with_skin(examples, &Printer::make_skin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe]), "//4=");
+    }
+}
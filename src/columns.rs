@@ -0,0 +1,164 @@
+use {
+    crate::template_check::sub_loop_opening,
+    std::{borrow::Cow, ops::Range},
+    termimad::Alignment,
+};
+
+/// The cells of a table line of a template (eg `|${short}|${long}|`)
+fn cells(line: &str) -> Option<Vec<&str>> {
+    let line = line.trim_end().strip_prefix('|')?;
+    let line = line.strip_suffix('|').unwrap_or(line);
+    Some(line.split('|').collect())
+}
+
+/// Write the cells as a table line
+fn push_cells(md: &mut String, cells: &[&str]) {
+    md.push('|');
+    md.push_str(&cells.join("|"));
+    md.push('|');
+}
+
+/// Whether the cell is the one of an alignment rule, eg `:-:`
+fn is_rule_cell(cell: &str) -> bool {
+    let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+    !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+}
+
+/// The ranges of the lines of the tables of a template: groups of
+/// consecutive table lines and sub-loop lines
+fn table_ranges(lines: &[&str]) -> Vec<Range<usize>> {
+    let is_table_line =
+        |line: &str| line.starts_with('|') || sub_loop_opening(line).is_some() || line == "}";
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        let mut end = start;
+        while end < lines.len() && is_table_line(lines[end]) {
+            end += 1;
+        }
+        if end > start {
+            ranges.push(start..end);
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+/// Rewrite the template, the lines of the tables being
+/// changed by the given function
+fn rewrite_tables<'s>(
+    template: &'s str,
+    mut rewrite_table: impl FnMut(&[&str], &mut String),
+) -> Cow<'s, str> {
+    let lines: Vec<&str> = template.lines().collect();
+    let mut md = String::with_capacity(template.len());
+    let mut idx = 0;
+    for range in table_ranges(&lines) {
+        for line in &lines[idx..range.start] {
+            md.push_str(line);
+            md.push('\n');
+        }
+        rewrite_table(&lines[range.clone()], &mut md);
+        idx = range.end;
+    }
+    for line in &lines[idx..] {
+        md.push_str(line);
+        md.push('\n');
+    }
+    if !template.ends_with('\n') {
+        md.pop();
+    }
+    Cow::Owned(md)
+}
+
+/// Whether the table contains one of the given sub-loops
+fn has_sub_loop(table: &[&str], sub_loops: &[&str]) -> bool {
+    table
+        .iter()
+        .any(|line| sub_loop_opening(line).is_some_and(|name| sub_loops.contains(&name)))
+}
+
+/// Remove from the tables of the template which contain one of the
+/// given sub-loops (eg `option-lines`) the columns whose rows
+/// have a cell made of only one of the given placeholders
+/// (eg `${short}`), with the header and alignment cells of
+/// those columns.
+pub(crate) fn remove_columns<'s>(
+    template: &'s str,
+    sub_loops: &[&str],
+    placeholders: &[&str],
+) -> Cow<'s, str> {
+    if !placeholders.iter().any(|p| template.contains(p)) {
+        return Cow::Borrowed(template);
+    }
+    rewrite_tables(template, |table, md| {
+        let removed = if has_sub_loop(table, sub_loops) {
+            table
+                .iter()
+                .filter_map(|line| cells(line))
+                .find_map(|cells| {
+                    let idx: Vec<usize> = (0..cells.len())
+                        .filter(|&i| placeholders.contains(&cells[i].trim()))
+                        .collect();
+                    (!idx.is_empty()).then_some((cells.len(), idx))
+                })
+        } else {
+            None
+        };
+        for line in table {
+            match (&removed, cells(line)) {
+                (Some((count, idx)), Some(cells)) if cells.len() == *count => {
+                    let cells: Vec<&str> = cells
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(i, _)| !idx.contains(i))
+                        .map(|(_, cell)| cell)
+                        .collect();
+                    push_cells(md, &cells);
+                }
+                _ => md.push_str(line),
+            }
+            md.push('\n');
+        }
+    })
+}
+
+/// Change the alignment rules of the tables of the template which
+/// contain one of the given sub-loops (eg `option-lines`), the first
+/// alignments applying to the first columns
+pub(crate) fn align_columns<'s>(
+    template: &'s str,
+    sub_loops: &[&str],
+    alignments: &[Alignment],
+) -> Cow<'s, str> {
+    if alignments.is_empty() {
+        return Cow::Borrowed(template);
+    }
+    rewrite_tables(template, |table, md| {
+        let aligned = has_sub_loop(table, sub_loops);
+        for line in table {
+            match cells(line) {
+                Some(cells)
+                    if aligned && cells.len() > 1 && cells.iter().all(|c| is_rule_cell(c)) =>
+                {
+                    let cells: Vec<&str> = cells
+                        .iter()
+                        .enumerate()
+                        .map(|(i, cell)| match alignments.get(i) {
+                            Some(Alignment::Left) => ":-",
+                            Some(Alignment::Center) => ":-:",
+                            Some(Alignment::Right) => "-:",
+                            Some(Alignment::Unspecified) => "-",
+                            None => cell,
+                        })
+                        .collect();
+                    push_cells(md, &cells);
+                }
+                _ => md.push_str(line),
+            }
+            md.push('\n');
+        }
+    })
+}
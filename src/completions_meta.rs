@@ -0,0 +1,241 @@
+//! Descriptions of the arguments in a form completion generators can
+//! consume, so that the help strings written for the help also describe
+//! the flags in zsh or fish completions.
+
+use {
+    clap::{Arg, Command, ValueHint},
+    termimad::minimad::Composite,
+};
+
+/// The completion data of an option or positional argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgMeta {
+    pub id: String,
+    pub short: Option<char>,
+    pub long: Option<String>,
+    /// The first line of the help, without markdown
+    pub description: String,
+    /// The name of the value, eg `PATH`, when the argument takes one
+    pub value_name: Option<String>,
+    pub value_hint: ValueHint,
+    /// The visible possible values, with their descriptions
+    pub possible_values: Vec<(String, String)>,
+}
+
+/// The completion data of a command and of its subcommands, see [export]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionMeta {
+    pub name: String,
+    /// The first line of the about, without markdown
+    pub description: String,
+    pub args: Vec<ArgMeta>,
+    pub subcommands: Vec<CompletionMeta>,
+}
+
+/// Return the first line of a help string, without its markdown marks
+fn plain_description(help: &str) -> String {
+    let line = help.lines().next().unwrap_or_default();
+    let composite = Composite::from_inline(line);
+    composite
+        .compounds
+        .iter()
+        .map(|compound| compound.src)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Quote a string for a shell, between single quotes
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+impl ArgMeta {
+    fn of(arg: &Arg) -> Self {
+        let takes_value = arg.get_action().takes_values();
+        Self {
+            id: arg.get_id().to_string(),
+            short: arg.get_short(),
+            long: arg.get_long().map(str::to_string),
+            description: arg
+                .get_help()
+                .map(|help| plain_description(&help.to_string()))
+                .unwrap_or_default(),
+            // clap uses the id in uppercase when there's no value name
+            value_name: takes_value.then(|| {
+                arg.get_value_names()
+                    .and_then(|names| names.first())
+                    .map_or_else(
+                        || arg.get_id().as_str().to_uppercase(),
+                        |name| name.to_string(),
+                    )
+            }),
+            value_hint: arg.get_value_hint(),
+            possible_values: arg
+                .get_possible_values()
+                .iter()
+                .filter(|value| !value.is_hide_set())
+                .map(|value| {
+                    let help = value.get_help().map(|h| h.to_string()).unwrap_or_default();
+                    (value.get_name().to_string(), plain_description(&help))
+                })
+                .collect(),
+        }
+    }
+    pub fn is_positional(&self) -> bool {
+        self.short.is_none() && self.long.is_none()
+    }
+    /// The zsh action completing the value, eg `_files`
+    fn zsh_action(&self) -> String {
+        if !self.possible_values.is_empty() {
+            let values: Vec<String> = self
+                .possible_values
+                .iter()
+                .map(|(name, help)| {
+                    if help.is_empty() {
+                        name.replace(':', r"\:")
+                    } else {
+                        format!(
+                            "{}\\:\"{}\"",
+                            name.replace(':', r"\:"),
+                            help.replace('"', "")
+                        )
+                    }
+                })
+                .collect();
+            return format!("(({}))", values.join(" "));
+        }
+        match self.value_hint {
+            ValueHint::AnyPath | ValueHint::FilePath => "_files",
+            ValueHint::DirPath => "_files -/",
+            ValueHint::ExecutablePath => "_absolute_command_paths",
+            ValueHint::CommandName => "_command_names -e",
+            ValueHint::Username => "_users",
+            ValueHint::Hostname => "_hosts",
+            ValueHint::Url => "_urls",
+            ValueHint::EmailAddress => "_email_addresses",
+            _ => "",
+        }
+        .to_string()
+    }
+    /// The specs of the option for the `_arguments` function of zsh,
+    /// eg `'--width=[Width of the table]:WIDTH:'`
+    pub fn zsh_specs(&self) -> Vec<String> {
+        let description = self
+            .description
+            .replace('\\', r"\\")
+            .replace('[', r"\[")
+            .replace(']', r"\]")
+            .replace(':', r"\:");
+        let value = match &self.value_name {
+            Some(name) => format!(":{}:{}", name, self.zsh_action()),
+            None => String::new(),
+        };
+        if self.is_positional() {
+            let message = match (&self.value_name, description.is_empty()) {
+                (Some(name), true) => name.clone(),
+                _ => description,
+            };
+            return vec![shell_quote(&format!(":{message}:{}", self.zsh_action()))];
+        }
+        let mut specs = Vec::new();
+        if let Some(short) = self.short {
+            specs.push(format!("-{short}[{description}]{value}"));
+        }
+        if let Some(long) = &self.long {
+            let eq = if self.value_name.is_some() { "=" } else { "" };
+            specs.push(format!("--{long}{eq}[{description}]{value}"));
+        }
+        specs.iter().map(|spec| shell_quote(spec)).collect()
+    }
+    /// The `complete` command of fish for the option, eg
+    /// `complete -c mytool -s w -l width -r -d 'Width of the table'`
+    pub fn fish_line(&self, command: &str) -> Option<String> {
+        if self.is_positional() {
+            return None;
+        }
+        let mut line = format!("complete -c {command}");
+        if let Some(short) = self.short {
+            line.push_str(&format!(" -s {short}"));
+        }
+        if let Some(long) = &self.long {
+            line.push_str(&format!(" -l {long}"));
+        }
+        if self.value_name.is_some() {
+            line.push_str(" -r");
+            if !self.possible_values.is_empty() {
+                let values: Vec<&str> = self
+                    .possible_values
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect();
+                line.push_str(&format!(" -f -a {}", shell_quote(&values.join(" "))));
+            } else {
+                match self.value_hint {
+                    ValueHint::AnyPath | ValueHint::FilePath | ValueHint::ExecutablePath => {
+                        line.push_str(" -F");
+                    }
+                    ValueHint::DirPath => {
+                        line.push_str(" -f -a '(__fish_complete_directories)'");
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !self.description.is_empty() {
+            line.push_str(&format!(" -d {}", shell_quote(&self.description)));
+        }
+        Some(line)
+    }
+}
+
+impl CompletionMeta {
+    /// The `_arguments` specs of zsh for the arguments of this command
+    /// (not of its subcommands)
+    pub fn zsh_specs(&self) -> Vec<String> {
+        self.args.iter().flat_map(ArgMeta::zsh_specs).collect()
+    }
+    /// The `complete` commands of fish for the options of this command
+    /// (not of its subcommands)
+    pub fn fish_lines(&self) -> Vec<String> {
+        self.args
+            .iter()
+            .filter_map(|arg| arg.fish_line(&self.name))
+            .collect()
+    }
+}
+
+/// Export the descriptions and value hints of the visible arguments
+/// of the command and of its subcommands, recursively.
+///
+/// ```
+/// use clap::{Arg, Command};
+///
+/// let cmd = Command::new("mytool")
+///     .arg(Arg::new("width").short('w').long("width").help("Width of the **table**"));
+/// let meta = clap_help::completions_meta::export(&cmd);
+/// assert_eq!(meta.args[0].description, "Width of the table");
+/// assert_eq!(
+///     meta.args[0].fish_line("mytool").unwrap(),
+///     "complete -c mytool -s w -l width -r -d 'Width of the table'",
+/// );
+/// ```
+pub fn export(cmd: &Command) -> CompletionMeta {
+    CompletionMeta {
+        name: cmd.get_name().to_string(),
+        description: cmd
+            .get_about()
+            .map(|about| plain_description(&about.to_string()))
+            .unwrap_or_default(),
+        args: cmd
+            .get_arguments()
+            .filter(|arg| !arg.is_hide_set())
+            .map(ArgMeta::of)
+            .collect(),
+        subcommands: cmd
+            .get_subcommands()
+            .filter(|subcommand| !subcommand.is_hide_set())
+            .map(export)
+            .collect(),
+    }
+}
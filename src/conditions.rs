@@ -0,0 +1,111 @@
+use {
+    crate::template_check::{is_valid_name_char, sub_loop_opening},
+    std::borrow::Cow,
+};
+
+/// The conditions the printer evaluates, which templates may test
+/// without declaring them with `Printer::with_condition`
+pub(crate) const BUILTIN_CONDITIONS: &[&str] = &[
+    "has-options",
+    "has-flags",
+    "has-value-options",
+    "has-common-options",
+    "has-advanced-options",
+    "has-hidden-options",
+    "has-possible-values",
+    "has-badges",
+    "has-positionals",
+    "has-subcommands",
+    "has-plugins",
+    "has-tips",
+    "has-environment",
+    "help-all",
+    "has-author",
+    "has-version",
+];
+
+/// If the line opens a conditional block (eg `${?has-subcommands` or
+/// `${?!has-subcommands`), return its condition and whether it's negated
+pub(crate) fn condition_opening(line: &str) -> Option<(&str, bool)> {
+    let name = line.strip_prefix("${?")?;
+    let (name, negated) = match name.strip_prefix('!') {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    if !name.is_empty() && name.chars().all(is_valid_name_char) {
+        Some((name, negated))
+    } else {
+        None
+    }
+}
+
+/// Remove from the template the conditional blocks whose condition
+/// isn't verified, and the lines opening and closing the others.
+///
+/// A conditional block starts with a `${?condition` line (or
+/// `${?!condition` to negate it) and ends with a `}` line, like a
+/// sub-loop. Blocks and sub-loops can be nested.
+pub(crate) fn apply_conditions<'s>(
+    template: &'s str,
+    is_verified: impl Fn(&str) -> bool,
+) -> Cow<'s, str> {
+    if !template.contains("${?") {
+        return Cow::Borrowed(template);
+    }
+    let mut kept = String::with_capacity(template.len());
+    // the open blocks: Some(shown) for a conditional block, None for a sub-loop
+    let mut blocks: Vec<Option<bool>> = Vec::new();
+    for line in template.lines() {
+        let shown = blocks.iter().all(|block| block.unwrap_or(true));
+        if let Some((name, negated)) = condition_opening(line) {
+            blocks.push(Some(is_verified(name) != negated));
+            continue;
+        }
+        if sub_loop_opening(line).is_some() {
+            blocks.push(None);
+        } else if line == "}" && matches!(blocks.pop(), Some(Some(_))) {
+            continue;
+        }
+        if shown {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+    }
+    if !template.ends_with('\n') {
+        kept.pop();
+    }
+    Cow::Owned(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_verified_blocks() {
+        let template = "a\n${?has-x\nb\n}\n${?!has-x\nc\n}\nd\n";
+        assert_eq!(
+            apply_conditions(template, |name| name == "has-x"),
+            "a\nb\nd\n"
+        );
+        assert_eq!(apply_conditions(template, |_| false), "a\nc\nd\n");
+    }
+
+    #[test]
+    fn keep_sub_loops_of_blocks() {
+        let template = "${?has-y\n${option-lines\nx\n}\n}\nz";
+        assert_eq!(apply_conditions(template, |_| false), "z");
+        assert_eq!(
+            apply_conditions(template, |_| true),
+            "${option-lines\nx\n}\nz",
+        );
+    }
+
+    #[test]
+    fn borrow_templates_without_conditions() {
+        assert!(matches!(
+            apply_conditions("${option-lines\nx\n}", |_| true),
+            Cow::Borrowed(_),
+        ));
+    }
+}
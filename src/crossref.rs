@@ -0,0 +1,136 @@
+use {
+    clap::Command,
+    std::{borrow::Cow, fmt},
+};
+
+/// A reference, in a help string, to an option or a subcommand which
+/// doesn't exist, found by [crate::Printer::check_references]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenReference {
+    /// The option or subcommand whose help contains the reference,
+    /// eg `--output` or `build`
+    pub location: String,
+    /// The reference, eg `{--format}` or `[[subcmd:build]]`
+    pub reference: String,
+}
+
+impl fmt::Display for BrokenReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "help of {}: broken reference {}",
+            self.location, self.reference
+        )
+    }
+}
+
+/// A reference found in a help string
+struct Reference<'s> {
+    start: usize,
+    end: usize,
+    /// the name of the option (eg `--format`) or subcommand (eg `build`)
+    name: &'s str,
+    exists: bool,
+}
+
+fn is_option_name(s: &str) -> bool {
+    let name = s.strip_prefix("--").or_else(|| s.strip_prefix('-'));
+    name.is_some_and(|name| {
+        name.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    })
+}
+
+fn option_exists(cmd: &Command, name: &str) -> bool {
+    cmd.get_arguments()
+        .any(|arg| match name.strip_prefix("--") {
+            Some(long) => arg.get_long() == Some(long),
+            None => {
+                let short = &name[1..];
+                short.chars().count() == 1 && arg.get_short() == short.chars().next()
+            }
+        })
+}
+
+/// Find the `{--option}`, `{-o}` and `[[subcmd:name]]` references
+fn references<'s>(help: &'s str, cmd: &Command) -> Vec<Reference<'s>> {
+    let mut references = Vec::new();
+    let mut idx = 0;
+    while idx < help.len() {
+        let rest = &help[idx..];
+        if let Some(inner) = rest.strip_prefix("[[subcmd:") {
+            if let Some(len) = inner.find("]]") {
+                let name = &inner[..len];
+                let end = idx + "[[subcmd:".len() + len + 2;
+                references.push(Reference {
+                    start: idx,
+                    end,
+                    name,
+                    exists: cmd.find_subcommand(name).is_some(),
+                });
+                idx = end;
+                continue;
+            }
+        }
+        if let Some(inner) = rest.strip_prefix('{') {
+            if let Some(len) = inner.find('}') {
+                let name = &inner[..len];
+                if is_option_name(name) {
+                    let end = idx + len + 2;
+                    references.push(Reference {
+                        start: idx,
+                        end,
+                        name,
+                        exists: option_exists(cmd, name),
+                    });
+                    idx = end;
+                    continue;
+                }
+            }
+        }
+        idx += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    references
+}
+
+/// Replace the references to options (`{--format}` or `{-f}`) and
+/// subcommands (`[[subcmd:build]]`) of a help string with the styled
+/// names, or with the plain names when they don't exist
+pub(crate) fn resolve_references<'s>(help: &'s str, cmd: &Command) -> Cow<'s, str> {
+    let references = references(help, cmd);
+    if references.is_empty() {
+        return Cow::Borrowed(help);
+    }
+    let mut resolved = String::with_capacity(help.len());
+    let mut idx = 0;
+    for reference in references {
+        resolved.push_str(&help[idx..reference.start]);
+        if reference.exists {
+            resolved.push_str(&format!("**{}**", reference.name));
+        } else {
+            resolved.push_str(reference.name);
+        }
+        idx = reference.end;
+    }
+    resolved.push_str(&help[idx..]);
+    Cow::Owned(resolved)
+}
+
+/// Add the references of the help which don't exist in the command
+pub(crate) fn check_references(
+    location: &str,
+    help: &str,
+    cmd: &Command,
+    broken: &mut Vec<BrokenReference>,
+) {
+    for reference in references(help, cmd) {
+        if !reference.exists {
+            broken.push(BrokenReference {
+                location: location.to_string(),
+                reference: help[reference.start..reference.end].to_string(),
+            });
+        }
+    }
+}
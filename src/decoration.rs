@@ -0,0 +1,127 @@
+use {termimad::MadSkin, unicode_width::UnicodeWidthStr};
+
+/// A layout change applied to the rendered text of a section,
+/// see [crate::Printer::decorate_section]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Decoration {
+    /// Draw a box around the section, with the border chars
+    /// and color of the tables
+    Boxed,
+    /// Center the section in the terminal
+    Centered,
+}
+
+/// Return the width of the text, without its escape sequences
+fn visible_width(line: &str) -> usize {
+    visible_text(line).width()
+}
+
+/// Return the text without its escape sequences
+fn visible_text(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI sequence, eg a color, ending with a letter
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            // OSC sequence, eg a hyperlink, ending with BEL or ST
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Apply the decoration to the rendered text of a section.
+///
+/// The blank lines before and after the content are kept
+/// outside of the decoration.
+pub(crate) fn decorate(text: &str, decoration: Decoration, skin: &MadSkin, width: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let is_blank = |line: &&str| visible_width(line.trim_end()) == 0;
+    let start = lines
+        .iter()
+        .position(|l| !is_blank(l))
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !is_blank(l))
+        .map_or(start, |i| i + 1);
+    let content = &lines[start..end];
+    let content_width = content.iter().map(|l| visible_width(l)).max().unwrap_or(0);
+    let mut decorated = String::new();
+    for _ in 0..start {
+        decorated.push('\n');
+    }
+    match decoration {
+        Decoration::Boxed => {
+            let chars = skin.table_border_chars;
+            let style = &skin.table.compound_style;
+            let horizontal = chars.horizontal.to_string().repeat(content_width + 2);
+            decorated.push_str(&format!(
+                "{}\n",
+                style.apply_to(format!(
+                    "{}{horizontal}{}",
+                    chars.top_left_corner, chars.top_right_corner
+                )),
+            ));
+            for line in content {
+                let padding = " ".repeat(content_width - visible_width(line));
+                decorated.push_str(&format!(
+                    "{} {line}{padding} {}\n",
+                    style.apply_to(chars.vertical),
+                    style.apply_to(chars.vertical),
+                ));
+            }
+            decorated.push_str(&format!(
+                "{}\n",
+                style.apply_to(format!(
+                    "{}{horizontal}{}",
+                    chars.bottom_left_corner, chars.bottom_right_corner
+                )),
+            ));
+        }
+        Decoration::Centered => {
+            // the content may already be padded, eg a centered title
+            let texts: Vec<String> = content.iter().map(|l| visible_text(l)).collect();
+            let lead = texts
+                .iter()
+                .filter(|t| !t.trim().is_empty())
+                .map(|t| t.width() - t.trim_start().width())
+                .min()
+                .unwrap_or(0);
+            let end = texts
+                .iter()
+                .map(|t| t.trim_end().width())
+                .max()
+                .unwrap_or(0);
+            let start = width.saturating_sub(end - lead) / 2;
+            let margin = " ".repeat(start.saturating_sub(lead));
+            for line in content {
+                decorated.push_str(&margin);
+                decorated.push_str(line);
+                decorated.push('\n');
+            }
+        }
+    }
+    for _ in end..lines.len() {
+        decorated.push('\n');
+    }
+    decorated
+}
@@ -0,0 +1,34 @@
+/// Recognize the help of an argument written as deprecated, eg
+///
+/// - `[deprecated] Old name of --width`
+/// - `[deprecated: use --width] Width of the area`
+/// - `Deprecated: use --width instead`
+///
+/// and return the deprecation note and the remaining help.
+pub(crate) fn parse_deprecated_help(help: &str) -> Option<(&str, &str)> {
+    let help = help.trim_start();
+    for (open, close) in [('[', ']'), ('(', ')')] {
+        let Some(inner) = help.strip_prefix(open) else {
+            continue;
+        };
+        let Some(end) = inner.find(close) else {
+            continue;
+        };
+        let Some(note) = strip_deprecated(&inner[..end]) else {
+            continue;
+        };
+        let note = note.trim_start_matches(':').trim();
+        return Some((note, inner[end + close.len_utf8()..].trim_start()));
+    }
+    let note = strip_deprecated(help)?.strip_prefix(':')?;
+    Some((note.trim(), ""))
+}
+
+/// Remove a case insensitive "deprecated" prefix
+fn strip_deprecated(s: &str) -> Option<&str> {
+    const DEPRECATED: &str = "deprecated";
+    let prefix = s.get(..DEPRECATED.len())?;
+    prefix
+        .eq_ignore_ascii_case(DEPRECATED)
+        .then(|| &s[DEPRECATED.len()..])
+}
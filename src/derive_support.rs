@@ -0,0 +1,43 @@
+//! The checks the `HelpPrinter` derive generates, made against
+//! the lists of clap-help so that they can't diverge from them
+
+use crate::{preset::PRESETS, user_config::OPTIONS_LAYOUTS};
+
+/// Tell, at compile time, whether a style preset has this name
+pub const fn is_style_preset(name: &str) -> bool {
+    let mut i = 0;
+    while i < PRESETS.len() {
+        if str_eq(PRESETS[i].name, name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Tell, at compile time, whether an options layout has this name
+pub const fn is_options_layout(name: &str) -> bool {
+    let mut i = 0;
+    while i < OPTIONS_LAYOUTS.len() {
+        if str_eq(OPTIONS_LAYOUTS[i], name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,137 @@
+use {
+    std::sync::OnceLock,
+    termimad::crossterm::{terminal, tty::IsTty},
+    terminal_light::Rgb,
+};
+
+/// Width of the help when the terminal isn't queried
+pub(crate) const DEFAULT_WIDTH: usize = 80;
+
+/// Tell whether the terminal may be queried, which isn't the case
+/// with the `no-detect` feature or when the output isn't a terminal,
+/// as the answer would be slow to come or wrong
+pub(crate) fn can_query_terminal(output_is_tty: bool) -> bool {
+    !cfg!(feature = "no-detect") && output_is_tty
+}
+
+/// Return the width given by the `COLUMNS` environment variable,
+/// or the one of the terminal, or [DEFAULT_WIDTH] when it can't
+/// be queried or the answer is obviously wrong
+pub(crate) fn terminal_width(output_is_tty: bool) -> usize {
+    let columns = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|&columns| columns > 0);
+    if let Some(columns) = columns {
+        return columns;
+    }
+    if !can_query_terminal(output_is_tty) {
+        return DEFAULT_WIDTH;
+    }
+    match termimad::terminal_size().0 as usize {
+        0 => DEFAULT_WIDTH,
+        width => width,
+    }
+}
+
+/// The theme of the terminal, deduced from the luma of its background
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Theme {
+    Light,
+    Dark,
+    /// Unknown, or neither clearly light nor clearly dark
+    Other,
+}
+
+impl Theme {
+    pub(crate) fn of_luma(luma: Option<f32>) -> Self {
+        match luma {
+            Some(luma) if luma > 0.85 => Self::Light,
+            Some(luma) if luma < 0.2 => Self::Dark,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// How the luma of the background of the terminal is queried, to
+/// choose a light or dark skin, see [crate::Printer::with_luma_detection]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Detection {
+    /// Query the terminal and wait for its answer
+    Enabled,
+    /// Never query the terminal, the default skin being used
+    Disabled,
+    /// Query the terminal, but give up after the given number of
+    /// milliseconds, as some terminals never answer
+    Timeout(u64),
+}
+
+impl Default for Detection {
+    fn default() -> Self {
+        Self::Timeout(200)
+    }
+}
+
+/// The background color of the terminal, queried at most once per process
+static BACKGROUND: OnceLock<Option<Rgb>> = OnceLock::new();
+
+/// Return the luma of the background of the terminal, between
+/// 0 (black) and 1 (white), if it can be queried and determined
+pub(crate) fn terminal_luma() -> Option<f32> {
+    terminal_luma_with(Detection::default(), true)
+}
+
+/// Return the luma of the background of the terminal, querying it
+/// the first time according to the detection policy, when the output
+/// is a terminal. As the query is written on stdout, it must be a
+/// terminal too.
+pub(crate) fn terminal_luma_with(detection: Detection, output_is_tty: bool) -> Option<f32> {
+    background_with(detection, output_is_tty).map(|rgb| rgb.luma())
+}
+
+/// Return the background color of the terminal, queried like in
+/// [terminal_luma_with], or black when it can't be determined
+pub(crate) fn terminal_background(detection: Detection) -> Rgb {
+    background_with(detection, true).unwrap_or(Rgb::new(0, 0, 0))
+}
+
+/// Return the background color of the terminal, querying it the
+/// first time according to the detection policy
+fn background_with(detection: Detection, output_is_tty: bool) -> Option<Rgb> {
+    let stdout_is_tty = std::io::stdout().is_tty();
+    if detection == Detection::Disabled || !can_query_terminal(output_is_tty && stdout_is_tty) {
+        return None;
+    }
+    *BACKGROUND.get_or_init(|| match detection {
+        Detection::Timeout(ms) => query_background(ms),
+        _ => terminal_light::background_color()
+            .ok()
+            .map(|color| color.rgb()),
+    })
+}
+
+/// Query the background color of the terminal, waiting at most the
+/// given number of milliseconds for its answer, falling back to the
+/// `COLORFGBG` environment variable
+fn query_background(timeout_ms: u64) -> Option<Rgb> {
+    let switch_to_raw = !terminal::is_raw_mode_enabled().ok()?;
+    if switch_to_raw {
+        terminal::enable_raw_mode().ok()?;
+    }
+    let answer = xterm_query::query_osc("\x1b]11;?\x07", timeout_ms);
+    if switch_to_raw {
+        let _ = terminal::disable_raw_mode();
+    }
+    // the answer is like `]11;rgb:3838/a4a4/c9c9`, of which we
+    // read the most significant digits of each component
+    let rgb = answer.ok().and_then(|answer| {
+        let raw = answer.strip_prefix("]11;rgb:")?;
+        let component = |start: usize| u8::from_str_radix(raw.get(start..start + 2)?, 16).ok();
+        Some(Rgb::new(component(0)?, component(5)?, component(10)?))
+    });
+    rgb.or_else(|| {
+        terminal_light::env::bg_color()
+            .ok()
+            .map(|ansi| terminal_light::Color::Ansi(ansi).rgb())
+    })
+}
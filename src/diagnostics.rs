@@ -0,0 +1,114 @@
+//! A report of what's detected of the terminal, to be joined to bug
+//! reports when the width or the skin of the help isn't the right one.
+
+use {
+    crate::{
+        detection::{can_query_terminal, terminal_luma, terminal_width, Theme},
+        legacy_colors::legacy_colors_needed,
+        target::print_md,
+        Printer,
+    },
+    std::env,
+    termimad::crossterm::tty::IsTty,
+};
+
+/// What's known of the terminal and what's chosen for the help
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalReport {
+    pub stdout_is_tty: bool,
+    /// Whether the terminal may be queried, which isn't the case with
+    /// the `no-detect` feature or when the output is redirected
+    pub queryable: bool,
+    /// The size given by the terminal, in columns and rows
+    pub terminal_size: Option<(u16, u16)>,
+    /// The value of the `COLUMNS` environment variable
+    pub columns_env: Option<String>,
+    /// The width used for the help
+    pub width: usize,
+    /// The luma of the background, between 0 (black) and 1 (white)
+    pub luma: Option<f32>,
+    /// Whether `COLORTERM` tells the terminal renders 24-bit colors
+    pub truecolor: bool,
+    /// Whether `NO_COLOR` is set to a non empty value
+    pub no_color: bool,
+    /// Whether the colors are downgraded to the 16 legacy ones
+    pub legacy_colors: bool,
+    /// The default skin chosen by [Printer::make_skin]: "light",
+    /// "dark", or "default"
+    pub default_skin: &'static str,
+}
+
+impl TerminalReport {
+    /// Query the terminal and the environment
+    pub fn detect() -> Self {
+        let stdout_is_tty = std::io::stdout().is_tty();
+        let queryable = can_query_terminal(stdout_is_tty);
+        let luma = terminal_luma();
+        let default_skin = match Theme::of_luma(luma) {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::Other => "default",
+        };
+        Self {
+            stdout_is_tty,
+            queryable,
+            terminal_size: queryable.then(termimad::terminal_size),
+            columns_env: env::var("COLUMNS").ok(),
+            width: terminal_width(stdout_is_tty),
+            luma,
+            truecolor: env::var("COLORTERM")
+                .is_ok_and(|value| value == "truecolor" || value == "24bit"),
+            no_color: env::var("NO_COLOR").is_ok_and(|value| !value.is_empty()),
+            legacy_colors: legacy_colors_needed(),
+            default_skin,
+        }
+    }
+    /// Write the report as a markdown table
+    pub fn to_markdown(&self) -> String {
+        let unknown = || "*unknown*".to_string();
+        let rows = [
+            ("clap-help version", env!("CARGO_PKG_VERSION").to_string()),
+            ("stdout is a terminal", self.stdout_is_tty.to_string()),
+            ("terminal queried", self.queryable.to_string()),
+            (
+                "terminal size",
+                self.terminal_size
+                    .map_or_else(unknown, |(w, h)| format!("{w}x{h}")),
+            ),
+            (
+                "`COLUMNS`",
+                self.columns_env
+                    .clone()
+                    .unwrap_or_else(|| "*not set*".to_string()),
+            ),
+            ("help width", self.width.to_string()),
+            (
+                "background luma",
+                self.luma.map_or_else(unknown, |luma| format!("{luma:.2}")),
+            ),
+            ("true colors", self.truecolor.to_string()),
+            ("`NO_COLOR`", self.no_color.to_string()),
+            ("legacy colors", self.legacy_colors.to_string()),
+            ("default skin", self.default_skin.to_string()),
+        ];
+        let mut md = String::from("|:-|:-|\n");
+        for (key, value) in rows {
+            md.push_str(&format!("|{key}|{value}|\n"));
+        }
+        md.push_str("|-\n");
+        md
+    }
+}
+
+/// Print what's detected of the terminal, and the skin chosen,
+/// eg to be pasted in a bug report.
+///
+/// ```no_run
+/// if std::env::args().any(|arg| arg == "--help-diagnostics") {
+///     clap_help::diagnostics::print_report();
+/// }
+/// ```
+pub fn print_report() {
+    let report = TerminalReport::detect();
+    print_md(&Printer::make_skin(), &report.to_markdown());
+}
@@ -0,0 +1,263 @@
+//! Differences between two versions of a command, eg to generate
+//! the "CLI changes" section of release notes.
+
+use {
+    crate::{arg_name::arg_name, target::print_md, Locale},
+    clap::{Arg, Command},
+    termimad::MadSkin,
+};
+
+/// The kind of a change between two versions of a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// The change of an argument or of a subcommand
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// The name of the argument (eg `--format` or `<FILE>`) or of the
+    /// subcommand, prefixed with the names of the parent subcommands
+    pub name: String,
+    /// The help of an added or removed item, or the list of the
+    /// changes of a changed one, as markdown
+    pub description: String,
+}
+
+/// The changes between two versions of a command, see [command_diff]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct HelpDiff {
+    pub changes: Vec<Change>,
+}
+
+impl HelpDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+    /// Return the changes of the given kind
+    pub fn changes_of(&self, kind: ChangeKind) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(move |change| change.kind == kind)
+    }
+    /// Write the changes as markdown, in an "Added", a "Removed" and a
+    /// "Changed" lists, with the labels of the locale
+    pub fn to_markdown(&self, locale: &Locale) -> String {
+        let mut md = String::new();
+        for (kind, key) in [
+            (ChangeKind::Added, "added-label"),
+            (ChangeKind::Removed, "removed-label"),
+            (ChangeKind::Changed, "changed-label"),
+        ] {
+            let mut changes = self.changes_of(kind).peekable();
+            if changes.peek().is_none() {
+                continue;
+            }
+            if !md.is_empty() {
+                md.push('\n');
+            }
+            md.push_str(&format!("**{}**\n", locale.get(key).unwrap_or(key)));
+            for change in changes {
+                md.push_str(&format!("* `{}`", change.name));
+                if !change.description.is_empty() {
+                    md.push_str(&format!(" : {}", change.description));
+                }
+                md.push('\n');
+            }
+        }
+        md
+    }
+}
+
+fn arg_help(arg: &Arg) -> String {
+    arg.get_help()
+        .map(|help| help.to_string())
+        .unwrap_or_default()
+}
+
+/// Describe the changes of an argument present in both versions
+fn arg_changes(old: &Arg, new: &Arg) -> Vec<String> {
+    let mut changes = Vec::new();
+    let (old_name, new_name) = (arg_name(old), arg_name(new));
+    if old_name != new_name {
+        changes.push(format!("renamed from `{old_name}`"));
+    }
+    if old.get_short() != new.get_short() {
+        match new.get_short() {
+            Some(short) => changes.push(format!("short name `-{short}`")),
+            None => changes.push("no short name".to_string()),
+        }
+    }
+    let value_names = |arg: &Arg| -> Vec<String> {
+        arg.get_value_names()
+            .unwrap_or_default()
+            .iter()
+            .map(|name| format!("<{name}>"))
+            .collect()
+    };
+    if old.get_action().takes_values() && new.get_action().takes_values() {
+        let (old_values, new_values) = (value_names(old), value_names(new));
+        if old_values != new_values {
+            changes.push(format!(
+                "value `{}` → `{}`",
+                old_values.join(" "),
+                new_values.join(" "),
+            ));
+        }
+    }
+    if old.is_required_set() != new.is_required_set() {
+        changes.push(if new.is_required_set() {
+            "now required".to_string()
+        } else {
+            "not required anymore".to_string()
+        });
+    }
+    let defaults = |arg: &Arg| -> String {
+        arg.get_default_values()
+            .iter()
+            .map(|value| value.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (old_default, new_default) = (defaults(old), defaults(new));
+    if old_default != new_default {
+        match (old_default.is_empty(), new_default.is_empty()) {
+            (true, _) => changes.push(format!("default `{new_default}`")),
+            (false, true) => changes.push("no default".to_string()),
+            (false, false) => {
+                changes.push(format!("default `{old_default}` → `{new_default}`"));
+            }
+        }
+    }
+    let possible_values = |arg: &Arg| -> Vec<String> {
+        arg.get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect()
+    };
+    let (old_values, new_values) = (possible_values(old), possible_values(new));
+    let values_list = |values: Vec<&String>| -> String {
+        values
+            .iter()
+            .map(|value| format!("`{value}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let added: Vec<&String> = new_values
+        .iter()
+        .filter(|v| !old_values.contains(v))
+        .collect();
+    if !added.is_empty() {
+        changes.push(format!("new values {}", values_list(added)));
+    }
+    let removed: Vec<&String> = old_values
+        .iter()
+        .filter(|v| !new_values.contains(v))
+        .collect();
+    if !removed.is_empty() {
+        changes.push(format!("removed values {}", values_list(removed)));
+    }
+    if arg_help(old) != arg_help(new) {
+        changes.push(format!("help: {}", arg_help(new)));
+    }
+    changes
+}
+
+fn diff_into(old: &Command, new: &Command, prefix: &str, diff: &mut HelpDiff) {
+    let is_shown = |arg: &&Arg| !arg.is_hide_set();
+    for new_arg in new.get_arguments().filter(is_shown) {
+        let old_arg = old
+            .get_arguments()
+            .filter(is_shown)
+            .find(|a| a.get_id() == new_arg.get_id());
+        match old_arg {
+            Some(old_arg) => {
+                let changes = arg_changes(old_arg, new_arg);
+                if !changes.is_empty() {
+                    diff.changes.push(Change {
+                        kind: ChangeKind::Changed,
+                        name: format!("{prefix}{}", arg_name(new_arg)),
+                        description: changes.join(", "),
+                    });
+                }
+            }
+            None => diff.changes.push(Change {
+                kind: ChangeKind::Added,
+                name: format!("{prefix}{}", arg_name(new_arg)),
+                description: arg_help(new_arg),
+            }),
+        }
+    }
+    for old_arg in old.get_arguments().filter(is_shown) {
+        if !new
+            .get_arguments()
+            .filter(is_shown)
+            .any(|a| a.get_id() == old_arg.get_id())
+        {
+            diff.changes.push(Change {
+                kind: ChangeKind::Removed,
+                name: format!("{prefix}{}", arg_name(old_arg)),
+                description: arg_help(old_arg),
+            });
+        }
+    }
+    let about = |cmd: &Command| cmd.get_about().map(|a| a.to_string()).unwrap_or_default();
+    for new_sub in new.get_subcommands().filter(|s| !s.is_hide_set()) {
+        let name = format!("{prefix}{}", new_sub.get_name());
+        match old.find_subcommand(new_sub.get_name()) {
+            Some(old_sub) => {
+                let (old_about, new_about) = (about(old_sub), about(new_sub));
+                if old_about != new_about {
+                    diff.changes.push(Change {
+                        kind: ChangeKind::Changed,
+                        name: name.clone(),
+                        description: format!("help: {new_about}"),
+                    });
+                }
+                diff_into(old_sub, new_sub, &format!("{name} "), diff);
+            }
+            None => diff.changes.push(Change {
+                kind: ChangeKind::Added,
+                name,
+                description: about(new_sub),
+            }),
+        }
+    }
+    for old_sub in old.get_subcommands().filter(|s| !s.is_hide_set()) {
+        if new.find_subcommand(old_sub.get_name()).is_none() {
+            diff.changes.push(Change {
+                kind: ChangeKind::Removed,
+                name: format!("{prefix}{}", old_sub.get_name()),
+                description: about(old_sub),
+            });
+        }
+    }
+}
+
+/// Compute the changes of the visible arguments and subcommands
+/// between two versions of a command, recursively.
+///
+/// ```
+/// use clap::{Arg, ArgAction, Command};
+/// use clap_help::diff::{command_diff, ChangeKind};
+///
+/// let old = Command::new("app").arg(Arg::new("fast").long("fast").action(ArgAction::SetTrue));
+/// let new = Command::new("app").arg(Arg::new("turbo").long("turbo").action(ArgAction::SetTrue));
+/// let diff = command_diff(&old, &new);
+/// assert_eq!(diff.changes_of(ChangeKind::Added).next().unwrap().name, "--turbo");
+/// assert_eq!(diff.changes_of(ChangeKind::Removed).next().unwrap().name, "--fast");
+/// ```
+pub fn command_diff(old: &Command, new: &Command) -> HelpDiff {
+    let mut diff = HelpDiff::default();
+    diff_into(old, new, "", &mut diff);
+    diff
+}
+
+/// Print the changes with the skin, eg the one of the help
+pub fn print_diff(diff: &HelpDiff, skin: &MadSkin) {
+    print_md(skin, &diff.to_markdown(&Locale::default()));
+}
@@ -0,0 +1,116 @@
+use {
+    termimad::{
+        minimad::{Alignment, Compound, Line, Text},
+        CompositeKind, FmtComposite, FmtLine, FmtText,
+    },
+    unicode_bidi::BidiInfo,
+    unicode_segmentation::UnicodeSegmentation,
+};
+
+/// The direction of the texts of the help
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+    #[default]
+    LeftToRight,
+    /// For right-to-left scripts, eg Arabic or Hebrew: tables are
+    /// mirrored, lines are right aligned, and the texts are reordered
+    /// with the unicode bidirectional algorithm
+    RightToLeft,
+}
+
+/// Mirror the tables of the text: reverse the order of their
+/// columns and swap left and right alignments
+pub(crate) fn mirror_tables(text: &mut Text<'_>) {
+    for line in &mut text.lines {
+        match line {
+            Line::TableRow(row) => {
+                row.cells.reverse();
+            }
+            Line::TableRule(rule) => {
+                rule.cells.reverse();
+                for align in &mut rule.cells {
+                    *align = match align {
+                        Alignment::Left | Alignment::Unspecified => Alignment::Right,
+                        Alignment::Right => Alignment::Left,
+                        Alignment::Center => Alignment::Center,
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reorder the lines of a wrapped text from logical to visual order,
+/// the direction of each line being given by its first strong char
+/// (so that a line of options, like `--help`, isn't reversed).
+/// List items get their bullet at the end.
+pub(crate) fn reorder_lines(text: &mut FmtText<'_, '_>) {
+    for line in &mut text.lines {
+        match line {
+            FmtLine::Normal(fc) => {
+                reorder_composite(fc);
+                let (end, depth) = match fc.kind {
+                    CompositeKind::ListItem(depth) => (" •", depth),
+                    CompositeKind::ListItemFollowUp(depth) => ("  ", depth),
+                    _ => continue,
+                };
+                fc.kind = CompositeKind::Paragraph;
+                fc.compounds.push(Compound::raw_str(end));
+                for _ in 0..depth {
+                    fc.compounds.push(Compound::raw_str(" "));
+                }
+                fc.recompute_width(text.skin);
+            }
+            FmtLine::TableRow(row) => {
+                for cell in &mut row.cells {
+                    reorder_composite(cell);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn reorder_composite(fc: &mut FmtComposite<'_>) {
+    fc.compounds.retain(|c| !c.src.is_empty());
+    let src: String = fc.compounds.iter().map(|c| c.src).collect();
+    if src.is_empty() {
+        return;
+    }
+    // start of each compound in src
+    let mut starts = Vec::with_capacity(fc.compounds.len());
+    let mut start = 0;
+    for compound in &fc.compounds {
+        starts.push(start);
+        start += compound.src.len();
+    }
+    let bidi = BidiInfo::new(&src, None);
+    let mut compounds = Vec::with_capacity(fc.compounds.len());
+    // push the part of src in [start, end[, which doesn't cross compounds
+    let mut push = |start: usize, end: usize| {
+        let idx = starts.partition_point(|&s| s <= start) - 1;
+        let compound = &fc.compounds[idx];
+        let end = end.min(starts[idx] + compound.src.len());
+        compounds.push(compound.sub(start - starts[idx], end - starts[idx]));
+    };
+    for para in &bidi.paragraphs {
+        let (levels, runs) = bidi.visual_runs(para, para.range.clone());
+        for run in runs {
+            if levels[run.start].is_rtl() {
+                for (offset, grapheme) in src[run.clone()].grapheme_indices(true).rev() {
+                    let start = run.start + offset;
+                    push(start, start + grapheme.len());
+                }
+            } else {
+                let mut start = run.start;
+                for &compound_start in starts.iter().filter(|&&s| s > run.start && s < run.end) {
+                    push(start, compound_start);
+                    start = compound_start;
+                }
+                push(start, run.end);
+            }
+        }
+    }
+    fc.compounds = compounds;
+}
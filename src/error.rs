@@ -0,0 +1,54 @@
+use {
+    crate::TemplateIssue,
+    std::{fmt, io},
+};
+
+/// Error returned by the fallible printing functions,
+/// eg [crate::Printer::try_print_help]
+#[derive(Debug)]
+pub enum Error {
+    /// The help couldn't be written, eg because the pipe
+    /// was closed
+    Io(io::Error),
+    /// Some templates are invalid, see [crate::Printer::check_templates]
+    Template(Vec<TemplateIssue>),
+}
+
+impl Error {
+    /// Tell whether the error comes from the reader of the
+    /// output having gone away, eg in `mytool --help | head -1`,
+    /// which most applications don't report
+    pub fn is_broken_pipe(&self) -> bool {
+        matches!(self, Self::Io(e) if e.kind() == io::ErrorKind::BrokenPipe)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to write the help: {e}"),
+            Self::Template(issues) => {
+                write!(f, "invalid template")?;
+                for issue in issues {
+                    write!(f, "\n{issue}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Template(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
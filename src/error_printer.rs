@@ -0,0 +1,173 @@
+use {
+    crate::{detection::terminal_width, Locale, Printer, Target},
+    clap::error::{ContextKind, ContextValue, Error},
+    std::io::{self, Write},
+    termimad::{
+        minimad::{OwningTemplateExpander, TextTemplate},
+        FmtText, MadSkin,
+    },
+};
+
+/// Default template of the errors printed by an [ErrorPrinter]
+pub static TEMPLATE_CLAP_ERROR: &str = "
+**${error-label}** *${error-kind}*
+
+${error}
+${suggestion-lines
+*${did-you-mean-label}* `${suggestion}`
+}
+${usage-lines
+**${usage-label}** `${usage}`
+}
+";
+
+/// A printer of the errors of clap, with a skin and labels
+/// consistent with the help.
+///
+/// The arguments and values quoted in the message of clap are
+/// displayed as inline code.
+///
+/// ```no_run
+/// use clap::{CommandFactory, FromArgMatches, Parser};
+///
+/// #[derive(Parser, Debug)]
+/// struct Args {
+///     #[arg(long)]
+///     width: Option<u16>,
+/// }
+///
+/// let printer = clap_help::Printer::new(Args::command());
+/// let args = Args::command()
+///     .try_get_matches()
+///     .and_then(|matches| Args::from_arg_matches(&matches))
+///     .unwrap_or_else(|err| printer.error_printer().exit(&err));
+/// ```
+pub struct ErrorPrinter<'t> {
+    skin: MadSkin,
+    locale: Locale,
+    template: &'t str,
+}
+
+impl Default for ErrorPrinter<'_> {
+    fn default() -> Self {
+        Self {
+            skin: Printer::make_skin(),
+            locale: Locale::default(),
+            template: TEMPLATE_CLAP_ERROR,
+        }
+    }
+}
+
+impl<'t> ErrorPrinter<'t> {
+    pub fn with_skin(mut self, skin: MadSkin) -> Self {
+        self.skin = skin;
+        self
+    }
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+    /// Use another template, with the `error-kind`, `error` variables,
+    /// and the `suggestion-lines` and `usage-lines` sub-loops
+    pub fn with_template(mut self, template: &'t str) -> Self {
+        self.template = template;
+        self
+    }
+
+    fn expander(&self, err: &Error) -> OwningTemplateExpander<'static> {
+        let mut expander = OwningTemplateExpander::new();
+        expander.set_default("");
+        for (key, text) in self.locale.labels() {
+            expander.set(key, text);
+        }
+        if let Some(kind) = err.kind().as_str() {
+            let mut chars = kind.chars();
+            if let Some(first) = chars.next() {
+                expander.set(
+                    "error-kind",
+                    first.to_uppercase().chain(chars).collect::<String>(),
+                );
+            }
+        }
+        let rendered = err.render().to_string();
+        let message = rendered.lines().next().unwrap_or_default();
+        let message = message.strip_prefix("error: ").unwrap_or(message);
+        expander.set_md("error", quotes_to_code(message));
+        for kind in [
+            ContextKind::SuggestedArg,
+            ContextKind::SuggestedSubcommand,
+            ContextKind::SuggestedValue,
+        ] {
+            match err.get(kind) {
+                Some(ContextValue::String(suggestion)) => {
+                    expander
+                        .sub("suggestion-lines")
+                        .set("suggestion", suggestion);
+                }
+                Some(ContextValue::Strings(suggestions)) => {
+                    for suggestion in suggestions {
+                        expander
+                            .sub("suggestion-lines")
+                            .set("suggestion", suggestion);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(ContextValue::StyledStr(usage)) = err.get(ContextKind::Usage) {
+            let usage = usage.to_string();
+            let usage = usage.trim();
+            let usage = usage.strip_prefix("Usage:").unwrap_or(usage).trim();
+            expander.sub("usage-lines").set("usage", usage);
+        }
+        expander
+    }
+
+    /// Render the error
+    pub fn error_text(&self, err: &Error) -> String {
+        let expander = self.expander(err);
+        let template = TextTemplate::from(self.template);
+        let text = expander.expand(&template);
+        let width = terminal_width(Target::Stderr.is_tty());
+        FmtText::from_text(&self.skin, text, Some(width)).to_string()
+    }
+
+    /// Print the error on stderr, or, when it's not an error but
+    /// a request of the help or version, print it like clap would
+    pub fn print(&self, err: &Error) {
+        if err.use_stderr() {
+            let _ = write!(io::stderr(), "{}", self.error_text(err));
+        } else {
+            let _ = err.print();
+        }
+    }
+
+    /// Print the error, then exit with the code clap would use
+    pub fn exit(&self, err: &Error) -> ! {
+        self.print(err);
+        std::process::exit(err.exit_code());
+    }
+}
+
+/// Replace the 'quoted' parts of a message of clap with inline code
+fn quotes_to_code(message: &str) -> String {
+    let mut md = String::with_capacity(message.len());
+    let mut parts = message.split('\'');
+    if let Some(first) = parts.next() {
+        md.push_str(first);
+    }
+    let mut parts: Vec<&str> = parts.collect();
+    // an odd number of quotes: the last one isn't closing anything
+    let unpaired = (parts.len() % 2 == 1).then(|| parts.pop()).flatten();
+    for pair in parts.chunks(2) {
+        md.push('`');
+        md.push_str(pair[0]);
+        md.push('`');
+        md.push_str(pair[1]);
+    }
+    if let Some(rest) = unpaired {
+        md.push('\'');
+        md.push_str(rest);
+    }
+    md
+}
@@ -0,0 +1,127 @@
+use {
+    std::fmt,
+    termimad::minimad::{OwningSubTemplateExpander, OwningTemplateExpander},
+};
+
+/// The variables you set with [crate::Printer::overrides_mut].
+///
+/// They're kept apart from the ones the printer computes from the
+/// command, and applied after them every time the expander is built,
+/// so that they override them and survive the settings changed later.
+#[derive(Debug, Default, Clone, Hash)]
+pub struct ExpanderOverrides {
+    default_value: Option<String>,
+    ops: Vec<Op>,
+}
+
+/// The values of an element of a sub-loop you added with
+/// [ExpanderOverrides::sub]
+#[derive(Debug, Default, Clone, Hash)]
+pub struct SubExpanderOverrides {
+    /// name, value, and whether the value is markdown
+    values: Vec<(&'static str, String, bool)>,
+}
+
+#[derive(Debug, Clone, Hash)]
+enum Op {
+    Set(&'static str, String),
+    SetMd(&'static str, String),
+    SetLines(&'static str, String),
+    SetLinesMd(&'static str, String),
+    Sub(&'static str, SubExpanderOverrides),
+}
+
+impl ExpanderOverrides {
+    /// Set the value of the placeholders with no value, which is
+    /// an empty string by default
+    pub fn set_default<S: Into<String>>(&mut self, value: S) -> &mut Self {
+        self.default_value = Some(value.into());
+        self
+    }
+    /// Set a value, not interpreted as markdown
+    pub fn set<S: fmt::Display>(&mut self, name: &'static str, value: S) -> &mut Self {
+        self.ops.push(Op::Set(name, value.to_string()));
+        self
+    }
+    /// Set a markdown value
+    pub fn set_md<S: Into<String>>(&mut self, name: &'static str, value: S) -> &mut Self {
+        self.ops.push(Op::SetMd(name, value.into()));
+        self
+    }
+    /// Replace a placeholder with several lines
+    pub fn set_lines<S: Into<String>>(&mut self, name: &'static str, lines: S) -> &mut Self {
+        self.ops.push(Op::SetLines(name, lines.into()));
+        self
+    }
+    /// Replace a placeholder with several lines of markdown
+    pub fn set_lines_md<S: Into<String>>(&mut self, name: &'static str, md: S) -> &mut Self {
+        self.ops.push(Op::SetLinesMd(name, md.into()));
+        self
+    }
+    /// Add an element to a sub-loop, eg a line to `option-lines`,
+    /// after the ones of the printer
+    pub fn sub(&mut self, name: &'static str) -> &mut SubExpanderOverrides {
+        self.ops
+            .push(Op::Sub(name, SubExpanderOverrides::default()));
+        match self.ops.last_mut() {
+            Some(Op::Sub(_, sub)) => sub,
+            _ => unreachable!(),
+        }
+    }
+    /// Apply the overrides to an expander filled by the printer
+    pub(crate) fn apply(&self, expander: &mut OwningTemplateExpander<'static>) {
+        if let Some(value) = &self.default_value {
+            expander.set_default(value.as_str());
+        }
+        for op in &self.ops {
+            match op {
+                Op::Set(name, value) => {
+                    expander.set(name, value);
+                }
+                Op::SetMd(name, value) => {
+                    expander.set_md(name, value.as_str());
+                }
+                Op::SetLines(name, value) => {
+                    expander.set_lines(name, value.as_str());
+                }
+                Op::SetLinesMd(name, value) => {
+                    expander.set_lines_md(name, value.as_str());
+                }
+                Op::Sub(name, sub) => sub.apply(expander.sub(name)),
+            }
+        }
+    }
+}
+
+impl SubExpanderOverrides {
+    /// Set a value, not interpreted as markdown
+    pub fn set<S: fmt::Display>(&mut self, name: &'static str, value: S) -> &mut Self {
+        self.values.push((name, value.to_string(), false));
+        self
+    }
+    /// Set a value when there's one
+    pub fn set_option<S: fmt::Display>(
+        &mut self,
+        name: &'static str,
+        value: Option<S>,
+    ) -> &mut Self {
+        if let Some(value) = value {
+            self.set(name, value);
+        }
+        self
+    }
+    /// Set a markdown value
+    pub fn set_md<S: Into<String>>(&mut self, name: &'static str, value: S) -> &mut Self {
+        self.values.push((name, value.into(), true));
+        self
+    }
+    fn apply(&self, sub: &mut OwningSubTemplateExpander<'static>) {
+        for (name, value, md) in &self.values {
+            if *md {
+                sub.set_md(name, value.as_str());
+            } else {
+                sub.set(name, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/// Height, in lines, of the glyphs of the embedded font
+const HEIGHT: usize = 5;
+
+/// The glyph of a char in the embedded font, for the letters,
+/// digits, and a few punctuation chars
+fn glyph(c: char) -> Option<[&'static str; HEIGHT]> {
+    Some(match c.to_ascii_uppercase() {
+        'A' => [" ### ", "#   #", "#####", "#   #", "#   #"],
+        'B' => ["#### ", "#   #", "#### ", "#   #", "#### "],
+        'C' => [" ####", "#    ", "#    ", "#    ", " ####"],
+        'D' => ["#### ", "#   #", "#   #", "#   #", "#### "],
+        'E' => ["#####", "#    ", "#### ", "#    ", "#####"],
+        'F' => ["#####", "#    ", "#### ", "#    ", "#    "],
+        'G' => [" ####", "#    ", "#  ##", "#   #", " ####"],
+        'H' => ["#   #", "#   #", "#####", "#   #", "#   #"],
+        'I' => ["#####", "  #  ", "  #  ", "  #  ", "#####"],
+        'J' => ["#####", "   # ", "   # ", "#  # ", " ##  "],
+        'K' => ["#   #", "#  # ", "###  ", "#  # ", "#   #"],
+        'L' => ["#    ", "#    ", "#    ", "#    ", "#####"],
+        'M' => ["#   #", "## ##", "# # #", "#   #", "#   #"],
+        'N' => ["#   #", "##  #", "# # #", "#  ##", "#   #"],
+        'O' => [" ### ", "#   #", "#   #", "#   #", " ### "],
+        'P' => ["#### ", "#   #", "#### ", "#    ", "#    "],
+        'Q' => [" ### ", "#   #", "# # #", "#  # ", " ## #"],
+        'R' => ["#### ", "#   #", "#### ", "#  # ", "#   #"],
+        'S' => [" ####", "#    ", " ### ", "    #", "#### "],
+        'T' => ["#####", "  #  ", "  #  ", "  #  ", "  #  "],
+        'U' => ["#   #", "#   #", "#   #", "#   #", " ### "],
+        'V' => ["#   #", "#   #", "#   #", " # # ", "  #  "],
+        'W' => ["#   #", "#   #", "# # #", "## ##", "#   #"],
+        'X' => ["#   #", " # # ", "  #  ", " # # ", "#   #"],
+        'Y' => ["#   #", " # # ", "  #  ", "  #  ", "  #  "],
+        'Z' => ["#####", "   # ", "  #  ", " #   ", "#####"],
+        '0' => [" ### ", "#  ##", "# # #", "##  #", " ### "],
+        '1' => ["  #  ", " ##  ", "  #  ", "  #  ", " ### "],
+        '2' => [" ### ", "#   #", "  ## ", " #   ", "#####"],
+        '3' => ["#### ", "    #", " ### ", "    #", "#### "],
+        '4' => ["#   #", "#   #", "#####", "    #", "    #"],
+        '5' => ["#####", "#    ", "#### ", "    #", "#### "],
+        '6' => [" ### ", "#    ", "#### ", "#   #", " ### "],
+        '7' => ["#####", "    #", "   # ", "  #  ", "  #  "],
+        '8' => [" ### ", "#   #", " ### ", "#   #", " ### "],
+        '9' => [" ### ", "#   #", " ####", "    #", " ### "],
+        '-' => ["   ", "   ", "###", "   ", "   "],
+        '_' => ["     ", "     ", "     ", "     ", "#####"],
+        '.' => [" ", " ", " ", " ", "#"],
+        ' ' => ["   ", "   ", "   ", "   ", "   "],
+        _ => return None,
+    })
+}
+
+/// Render the text as large ASCII art, with an embedded font.
+///
+/// Chars missing from the font (eg accented letters) are skipped.
+///
+/// ```
+/// let art = clap_help::figlet_text("hi");
+/// assert_eq!(art.lines().count(), 5);
+/// assert_eq!(art.lines().next(), Some("#   # #####"));
+/// ```
+pub fn figlet_text(text: &str) -> String {
+    let glyphs: Vec<_> = text.chars().filter_map(glyph).collect();
+    let mut art = String::new();
+    for row in 0..HEIGHT {
+        let line: Vec<&str> = glyphs.iter().map(|glyph| glyph[row]).collect();
+        art.push_str(line.join(" ").trim_end());
+        art.push('\n');
+    }
+    art
+}
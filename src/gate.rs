@@ -0,0 +1,77 @@
+use std::fmt;
+
+/// A condition for an argument to be available, see
+/// [crate::Printer::gate]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gate {
+    /// The argument is available only when the cargo feature, or the
+    /// unstable mode, of this name is enabled
+    RequiresFeature(&'static str),
+    /// The argument is available only on this platform,
+    /// see [crate::Printer::only_on]
+    OnlyOn(Platform),
+}
+
+impl Gate {
+    /// Tell whether the argument is available with these features,
+    /// on this platform
+    pub fn is_open(self, enabled_features: &[String], platform: Platform) -> bool {
+        match self {
+            Self::RequiresFeature(feature) => enabled_features.iter().any(|f| f == feature),
+            Self::OnlyOn(only) => only.includes(platform),
+        }
+    }
+}
+
+/// How the arguments whose gate is closed are displayed,
+/// see [crate::Printer::with_gate_display]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GateDisplay {
+    /// The arguments aren't displayed
+    #[default]
+    Hide,
+    /// The arguments are displayed with a note telling what they
+    /// require, as `${gate-note}`
+    Mark,
+}
+
+/// An operating system, or a family of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    Linux,
+    MacOs,
+    Windows,
+    /// Linux, macOS, the BSDs, and the other unix-like systems
+    Unix,
+}
+
+impl Platform {
+    /// The platform the application was compiled for, the most
+    /// specific one when several apply
+    pub fn current() -> Self {
+        if cfg!(target_os = "linux") {
+            Self::Linux
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Unix
+        }
+    }
+    /// Tell whether this platform is, or contains, the other one
+    pub fn includes(self, other: Self) -> bool {
+        self == other || (self == Self::Unix && matches!(other, Self::Linux | Self::MacOs))
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Linux => "Linux",
+            Self::MacOs => "macOS",
+            Self::Windows => "Windows",
+            Self::Unix => "Unix",
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use {
+    crate::{Section, StylePreset},
+    std::sync::OnceLock,
+};
+
+/// Whether the help is colored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ColorChoice {
+    /// Colors unless the `NO_COLOR` environment variable is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Tell whether the help is to be colored
+    pub fn is_colored(self) -> bool {
+        match self {
+            Self::Auto => std::env::var_os("NO_COLOR").map_or(true, |v| v.is_empty()),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// Defaults applied by [crate::Printer::new] to all printers of the
+/// process, once set with [set_global_defaults]
+#[derive(Debug, Clone, Default)]
+pub struct GlobalConfig {
+    /// The preset whose skin replaces the one matching the terminal
+    pub preset: Option<&'static StylePreset>,
+    /// See [crate::Printer::with_max_width]
+    pub max_width: Option<usize>,
+    pub color_choice: ColorChoice,
+    /// Templates of sections, replacing the default ones, custom sections
+    /// being added at the end of the section order. The sections depending
+    /// on the command, eg "options", are only changed when it has them.
+    pub templates: Vec<(Section, &'static str)>,
+}
+
+static GLOBAL_DEFAULTS: OnceLock<GlobalConfig> = OnceLock::new();
+
+/// Set the defaults of all the printers built afterwards, eg in a
+/// crate shared by the binaries of a workspace, so that they're
+/// configured in one call.
+///
+/// The defaults can be set only once: return false when they
+/// were already set.
+///
+/// ```
+/// use clap_help::{ColorChoice, GlobalConfig, StylePreset};
+///
+/// clap_help::set_global_defaults(GlobalConfig {
+///     preset: StylePreset::find("sunset"),
+///     max_width: Some(100),
+///     color_choice: ColorChoice::Auto,
+///     templates: vec![("bugs".into(), "Report bugs at https://example.com/issues\n")],
+/// });
+/// ```
+pub fn set_global_defaults(config: GlobalConfig) -> bool {
+    GLOBAL_DEFAULTS.set(config).is_ok()
+}
+
+pub(crate) fn global_defaults() -> Option<&'static GlobalConfig> {
+    GLOBAL_DEFAULTS.get()
+}
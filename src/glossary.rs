@@ -0,0 +1,71 @@
+use std::borrow::Cow;
+
+/// Tell whether the char can't be part of a word, so that a term
+/// isn't found inside a longer word
+fn is_boundary(c: Option<char>) -> bool {
+    c.map_or(true, |c| !c.is_alphanumeric() && c != '_' && c != '-')
+}
+
+/// Return the length of the term found at the start of the string,
+/// as a whole word, the longest term winning
+fn term_at(s: &str, before: Option<char>, terms: &[(String, String)]) -> Option<usize> {
+    if !is_boundary(before) {
+        return None;
+    }
+    terms
+        .iter()
+        .map(|(term, _)| term.as_str())
+        .filter(|term| !term.is_empty())
+        .filter(|term| {
+            s.get(..term.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(term))
+                && is_boundary(s[term.len()..].chars().next())
+        })
+        .map(str::len)
+        .max()
+}
+
+/// Italicize the occurrences of the glossary terms in a help string,
+/// except in inline code and in already styled parts
+pub(crate) fn italicize_terms<'s>(help: &'s str, terms: &[(String, String)]) -> Cow<'s, str> {
+    if terms.is_empty() {
+        return Cow::Borrowed(help);
+    }
+    let mut italicized = String::with_capacity(help.len() + 8);
+    let mut found = false;
+    let mut in_code = false;
+    let mut styled = false;
+    let mut before = None;
+    let mut idx = 0;
+    while let Some(c) = help[idx..].chars().next() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if (c == '*' || c == '~') && !in_code {
+            // a run of marks, eg `**`, opens or closes a styled part
+            let run = help[idx..].len() - help[idx..].trim_start_matches(c).len();
+            italicized.push_str(&help[idx..idx + run]);
+            styled = !styled;
+            before = Some(c);
+            idx += run;
+            continue;
+        } else if !in_code && !styled {
+            if let Some(len) = term_at(&help[idx..], before, terms) {
+                italicized.push('*');
+                italicized.push_str(&help[idx..idx + len]);
+                italicized.push('*');
+                found = true;
+                before = help[..idx + len].chars().next_back();
+                idx += len;
+                continue;
+            }
+        }
+        italicized.push(c);
+        before = Some(c);
+        idx += c.len_utf8();
+    }
+    if found {
+        Cow::Owned(italicized)
+    } else {
+        Cow::Borrowed(help)
+    }
+}
@@ -0,0 +1,119 @@
+use {
+    std::{env, fs, io::Write, path::PathBuf},
+    termimad::crossterm::tty::IsTty,
+};
+
+/// Height, in terminal rows, of the logo
+const LOGO_ROWS: usize = 5;
+
+/// Size of the chunks of base64 data sent to kitty
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// The image of a logo, which should be a PNG file as it's the
+/// format understood by all supported terminals
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ImageSource {
+    File(PathBuf),
+    /// The bytes of the image, eg from `include_bytes!`
+    Bytes(&'static [u8]),
+}
+
+impl ImageSource {
+    fn bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Self::File(path) => fs::read(path).ok(),
+            Self::Bytes(bytes) => Some(bytes.to_vec()),
+        }
+    }
+}
+
+/// A protocol to display images in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphicsProtocol {
+    /// The graphics protocol of kitty, also implemented by
+    /// ghostty and WezTerm
+    Kitty,
+    /// The inline images protocol of iTerm2
+    Iterm2,
+}
+
+/// Return the graphics protocol supported by the terminal, if it's
+/// a known one and the standard output is a terminal
+pub fn detect_graphics_protocol() -> Option<GraphicsProtocol> {
+    if !std::io::stdout().is_tty() {
+        return None;
+    }
+    let var = |name| env::var(name).unwrap_or_default();
+    match var("TERM_PROGRAM").as_str() {
+        "iTerm.app" | "WezTerm" => return Some(GraphicsProtocol::Iterm2),
+        "ghostty" => return Some(GraphicsProtocol::Kitty),
+        _ => {}
+    }
+    if var("TERM").contains("kitty") || env::var_os("KITTY_WINDOW_ID").is_some() {
+        return Some(GraphicsProtocol::Kitty);
+    }
+    None
+}
+
+/// Build the escape sequence displaying the image with the protocol,
+/// followed by the newline moving the cursor below the image
+fn image_sequence(bytes: &[u8], protocol: GraphicsProtocol) -> String {
+    let data = base64(bytes);
+    match protocol {
+        GraphicsProtocol::Kitty => {
+            let mut seq = String::new();
+            let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+            for (idx, chunk) in chunks.iter().enumerate() {
+                let more = u8::from(idx + 1 < chunks.len());
+                let chunk = String::from_utf8_lossy(chunk);
+                if idx == 0 {
+                    seq.push_str(&format!(
+                        "\x1b_Gf=100,a=T,r={LOGO_ROWS},m={more};{chunk}\x1b\\"
+                    ));
+                } else {
+                    seq.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+                }
+            }
+            seq.push('\n');
+            seq
+        }
+        GraphicsProtocol::Iterm2 => format!(
+            "\x1b]1337;File=inline=1;size={};height={LOGO_ROWS};preserveAspectRatio=1:{data}\x07\n",
+            bytes.len(),
+        ),
+    }
+}
+
+/// Print the logo when the terminal can display images,
+/// do nothing otherwise
+pub(crate) fn print_logo(source: &ImageSource, out: &mut impl Write) {
+    let Some(protocol) = detect_graphics_protocol() else {
+        return;
+    };
+    let Some(bytes) = source.bytes() else {
+        return;
+    };
+    let _ = out.write_all(image_sequence(&bytes, protocol).as_bytes());
+}
+
+/// Encode the bytes in standard base64, with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,213 @@
+use clap::{Arg, Command};
+
+/// The format in which the help is written, see
+/// [crate::Printer::print_help_in] and [crate::Printer::print_help_auto]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HelpFormat {
+    /// Styled for the terminal, as by [crate::Printer::print_help]
+    #[default]
+    Terminal,
+    /// The same layout, without any escape sequence
+    Plain,
+    /// The markdown of the expanded templates, eg for a docs generator
+    Markdown,
+    /// A description of the command and its arguments, for tools
+    Json,
+}
+
+impl HelpFormat {
+    /// Parse a format name, like `plain`, `markdown` or `json`
+    /// (case insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "terminal" | "tty" | "ansi" => Some(Self::Terminal),
+            "plain" | "text" | "txt" => Some(Self::Plain),
+            "markdown" | "md" => Some(Self::Markdown),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Remove the CSI sequences, eg styles, and the OSC sequences, eg
+/// hyperlinks, of a rendered text
+pub(crate) fn strip_escape_sequences(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                // parameters until the final byte, in @..=~
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                // until BEL or ST (ESC \\)
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    stripped
+}
+
+/// Write a string as a JSON string literal
+pub(crate) fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_option(s: Option<String>) -> String {
+    s.as_deref().map_or_else(|| "null".to_string(), json_string)
+}
+
+fn json_array(items: impl Iterator<Item = String>) -> String {
+    format!("[{}]", items.collect::<Vec<_>>().join(","))
+}
+
+fn arg_json(arg: &Arg) -> String {
+    let fields = [
+        ("id", json_string(arg.get_id().as_str())),
+        (
+            "short",
+            json_option(arg.get_short().map(|c| format!("-{c}"))),
+        ),
+        (
+            "long",
+            json_option(arg.get_long().map(|l| format!("--{l}"))),
+        ),
+        (
+            "value_names",
+            json_array(
+                arg.get_value_names()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|_| arg.get_action().takes_values())
+                    .map(|name| json_string(name)),
+            ),
+        ),
+        ("help", json_option(arg.get_help().map(|h| h.to_string()))),
+        ("required", arg.is_required_set().to_string()),
+        (
+            "default",
+            json_array(
+                arg.get_default_values()
+                    .iter()
+                    .map(|value| json_string(&value.to_string_lossy())),
+            ),
+        ),
+        (
+            "possible_values",
+            json_array(
+                arg.get_possible_values()
+                    .iter()
+                    .filter(|value| !value.is_hide_set())
+                    .map(|value| json_string(value.get_name())),
+            ),
+        ),
+        (
+            "env",
+            json_option(arg.get_env().map(|e| e.to_string_lossy().to_string())),
+        ),
+    ];
+    json_object(&fields)
+}
+
+fn json_object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{value}", json_string(key)))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Describe the command, its visible arguments and its subcommands,
+/// recursively, as JSON
+pub(crate) fn command_json(cmd: &Command) -> String {
+    let shown = |arg: &&Arg| !arg.is_hide_set();
+    let fields = [
+        (
+            "name",
+            json_string(cmd.get_bin_name().unwrap_or_else(|| cmd.get_name())),
+        ),
+        (
+            "version",
+            json_option(cmd.get_version().map(str::to_string)),
+        ),
+        ("about", json_option(cmd.get_about().map(|a| a.to_string()))),
+        (
+            "options",
+            json_array(
+                cmd.get_arguments()
+                    .filter(shown)
+                    .filter(|a| !a.is_positional())
+                    .map(arg_json),
+            ),
+        ),
+        (
+            "positionals",
+            json_array(cmd.get_positionals().filter(shown).map(arg_json)),
+        ),
+        (
+            "subcommands",
+            json_array(
+                cmd.get_subcommands()
+                    .filter(|s| !s.is_hide_set())
+                    .map(command_json),
+            ),
+        ),
+    ];
+    json_object(&fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_styles_and_hyperlinks() {
+        assert_eq!(strip_escape_sequences("\x1b[1;31mred\x1b[0m"), "red");
+        assert_eq!(
+            strip_escape_sequences("see \x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x07!"),
+            "see docs!",
+        );
+        assert_eq!(strip_escape_sequences("plain"), "plain");
+    }
+
+    #[test]
+    fn escape_json_strings() {
+        assert_eq!(json_string("abc"), r#""abc""#);
+        assert_eq!(
+            json_string("a\"b\\c\nd\te\u{1}é"),
+            r#""a\"b\\c\nd\te\u0001é""#,
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use clap::Arg;
+
+/// Sections which aren't displayed at the [HelpLevel::Short] level
+static SHORT_OMITTED_SECTIONS: &[&str] = &[
+    "summary",
+    "toc",
+    "subcommand-tree",
+    "changelog",
+    "keybindings",
+    "environment",
+    "files",
+    "glossary",
+    "tips",
+    "possible-values",
+    "exit-codes",
+    "links",
+];
+
+/// How much of the help is displayed, like the `-h` and `--help`
+/// of clap, see [crate::Printer::with_level]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HelpLevel {
+    /// Only the main sections, without the arguments hidden from
+    /// the short help (`hide_short_help`), usually for `-h`
+    Short,
+    /// All sections, without the arguments hidden from the long
+    /// help (`hide_long_help`), usually for `--help`
+    #[default]
+    Full,
+    /// All sections and all arguments, even the hidden ones
+    All,
+}
+
+impl HelpLevel {
+    /// Tell whether the argument is displayed at this level
+    pub(crate) fn shows_arg(self, arg: &Arg) -> bool {
+        match self {
+            Self::Short => !arg.is_hide_set() && !arg.is_hide_short_help_set(),
+            Self::Full => !arg.is_hide_set() && !arg.is_hide_long_help_set(),
+            Self::All => true,
+        }
+    }
+
+    /// Tell whether the section of this key is displayed at this level
+    pub(crate) fn shows_section(self, key: &str) -> bool {
+        self != Self::Short || !SHORT_OMITTED_SECTIONS.contains(&key)
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+/// Read a usage file, with one subcommand per line, as `name count`.
+///
+/// Empty lines, lines starting with `#`, and invalid lines are ignored.
+pub(crate) fn read_usage_counts(path: &Path) -> io::Result<Vec<(String, u64)>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (name, count) = line.rsplit_once(char::is_whitespace)?;
+            Some((name.trim().to_string(), count.parse().ok()?))
+        })
+        .collect())
+}
+
+/// Increment the count of a subcommand in a usage file, creating
+/// the file if needed, so that [crate::Printer::with_usage_stats]
+/// can list the most used subcommands first.
+///
+/// ```no_run
+/// // after having parsed the arguments and found the subcommand
+/// clap_help::record_subcommand_usage("/home/me/.local/state/mytool/usage", "build")
+///     .unwrap();
+/// ```
+pub fn record_subcommand_usage(path: impl AsRef<Path>, name: &str) -> io::Result<()> {
+    let path = path.as_ref();
+    let mut counts = match read_usage_counts(path) {
+        Ok(counts) => counts,
+        Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    match counts.iter_mut().find(|(n, _)| n == name) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((name.to_string(), 1)),
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content: String = counts
+        .iter()
+        .map(|(name, count)| format!("{name} {count}\n"))
+        .collect();
+    fs::write(path, content)
+}
@@ -0,0 +1,143 @@
+use {
+    crate::*,
+    clap::{
+        builder::{StyledStr, TypedValueParser},
+        Arg, ArgAction, Command,
+    },
+    std::{
+        ffi::OsStr,
+        io::{self, Write},
+        sync::Arc,
+    },
+};
+
+/// Replace the standard help of clap with clap-help's one, in the
+/// command and its subcommands.
+///
+/// This disables the help flag of clap, adds a `--help` flag (and `-h`
+/// when it's free), and returns the command. When this flag is found
+/// while parsing, the help of the matched (sub)command is printed and
+/// the program exits.
+///
+/// ```no_run
+/// use clap::{CommandFactory, FromArgMatches, Parser};
+///
+/// #[derive(Parser, Debug)]
+/// #[command(name = "my_prog", version, about)]
+/// struct Args {
+///     // your arguments
+/// }
+///
+/// let matches = clap_help::install(Args::command()).get_matches();
+/// let args = Args::from_arg_matches(&matches).unwrap();
+/// ```
+///
+/// If you already defined a `help` argument, it's kept and
+/// becomes the one triggering the help.
+///
+/// The help is printed by a default [Printer]: use [install_with]
+/// to print it with your settings.
+pub fn install(cmd: Command) -> Command {
+    install_with(cmd, |cmd| Printer::new(cmd.clone()))
+}
+
+/// Replace the standard help of clap with clap-help's one, like
+/// [install], the help being printed by the printer `make_printer`
+/// builds for the matched (sub)command.
+///
+/// ```no_run
+/// use clap::{CommandFactory, FromArgMatches, Parser};
+///
+/// #[derive(Parser, Debug)]
+/// #[command(name = "my_prog", version, about)]
+/// struct Args {
+///     // your arguments
+/// }
+///
+/// let cmd = clap_help::install_with(Args::command(), |cmd| {
+///     clap_help::Printer::new(cmd.clone())
+///         .with_max_width(100)
+///         .without("author")
+/// });
+/// let matches = cmd.get_matches();
+/// let args = Args::from_arg_matches(&matches).unwrap();
+/// ```
+pub fn install_with<F>(cmd: Command, make_printer: F) -> Command
+where
+    F: Fn(&Command) -> Printer<'static> + Clone + Send + Sync + 'static,
+{
+    install_parser(
+        cmd,
+        HelpFlagParser {
+            make_printer: Arc::new(make_printer),
+        },
+    )
+}
+
+fn install_parser(cmd: Command, parser: HelpFlagParser) -> Command {
+    let has_help_arg = cmd.get_arguments().any(|a| a.get_id() == "help");
+    let mut cmd = cmd.disable_help_flag(true);
+    if has_help_arg {
+        cmd = cmd.mut_arg("help", |arg| arg.value_parser(parser.clone()));
+    } else {
+        let short_is_free = cmd.get_arguments().all(|a| a.get_short() != Some('h'));
+        let mut arg = Arg::new("help")
+            .long("help")
+            .help("Print help")
+            .action(ArgAction::SetTrue)
+            .value_parser(parser.clone());
+        if short_is_free {
+            arg = arg.short('h');
+        }
+        cmd = cmd.arg(arg);
+    }
+    cmd.mut_subcommands(|sub| install_parser(sub, parser.clone()))
+}
+
+/// Render the help of the command, or of one of the subcommands of the
+/// command of the printer, with the settings of the printer, for clap's
+/// own help pipeline, eg in `Command::override_help`.
+///
+/// The escape sequences of the styles are removed by clap when
+/// the output doesn't support them.
+///
+/// ```
+/// use clap::{CommandFactory, Parser};
+///
+/// #[derive(Parser)]
+/// #[command(name = "my_prog")]
+/// struct Args {}
+///
+/// let printer = clap_help::Printer::new(Args::command());
+/// let help = clap_help::as_clap_help_string(&Args::command(), &printer);
+/// let cmd = Args::command().override_help(help);
+/// ```
+pub fn as_clap_help_string(cmd: &Command, printer: &Printer<'_>) -> StyledStr {
+    printer.help_text_of(cmd).into()
+}
+
+/// The value parser of the help flag, printing the help when
+/// the flag is set
+#[derive(Clone)]
+struct HelpFlagParser {
+    make_printer: Arc<dyn Fn(&Command) -> Printer<'static> + Send + Sync>,
+}
+
+impl TypedValueParser for HelpFlagParser {
+    type Value = bool;
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        _arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<bool, clap::Error> {
+        if value == "true" {
+            (self.make_printer)(cmd).print_help();
+            // exit doesn't run the destructors, so what's still
+            // buffered must be written now
+            let _ = io::stdout().flush();
+            std::process::exit(0);
+        }
+        Ok(false)
+    }
+}
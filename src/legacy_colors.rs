@@ -0,0 +1,119 @@
+use termimad::{
+    crossterm::style::{Color, ContentStyle},
+    MadSkin, StyledChar,
+};
+
+/// The 16 colors of legacy consoles, with their values in the
+/// default palette of the Windows console
+const LEGACY_COLORS: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [12, 12, 12]),
+    (Color::DarkRed, [197, 15, 31]),
+    (Color::DarkGreen, [19, 161, 14]),
+    (Color::DarkYellow, [193, 156, 0]),
+    (Color::DarkBlue, [0, 55, 218]),
+    (Color::DarkMagenta, [136, 23, 152]),
+    (Color::DarkCyan, [58, 150, 221]),
+    (Color::Grey, [204, 204, 204]),
+    (Color::DarkGrey, [118, 118, 118]),
+    (Color::Red, [231, 72, 86]),
+    (Color::Green, [22, 198, 12]),
+    (Color::Yellow, [249, 241, 165]),
+    (Color::Blue, [59, 120, 255]),
+    (Color::Magenta, [180, 0, 158]),
+    (Color::Cyan, [97, 214, 214]),
+    (Color::White, [242, 242, 242]),
+];
+
+/// Tell whether the terminal only renders the 16 legacy colors, which
+/// is the case of the consoles of old Windows versions.
+///
+/// On Windows, this also enables the processing of the escape
+/// sequences by the console, when possible.
+pub(crate) fn legacy_colors_needed() -> bool {
+    #[cfg(windows)]
+    {
+        termimad::crossterm::style::available_color_count() < 256
+    }
+    #[cfg(not(windows))]
+    {
+        false
+    }
+}
+
+/// Return the rgb value of a color of the 256 colors palette
+fn ansi_rgb(value: u8) -> [u8; 3] {
+    match value {
+        0..=15 => LEGACY_COLORS[value as usize].1,
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + 40 * n };
+            let n = value - 16;
+            [level(n / 36), level(n / 6 % 6), level(n % 6)]
+        }
+        _ => {
+            let gray = 8 + 10 * (value - 232);
+            [gray, gray, gray]
+        }
+    }
+}
+
+/// Return the legacy color nearest to the given one
+fn legacy_color(color: Color) -> Color {
+    let rgb = match color {
+        Color::AnsiValue(value) => ansi_rgb(value),
+        Color::Rgb { r, g, b } => [r, g, b],
+        _ => return color,
+    };
+    let distance = |other: &[u8; 3]| -> u32 {
+        rgb.iter()
+            .zip(other)
+            .map(|(&a, &b)| (i32::from(a) - i32::from(b)).pow(2) as u32)
+            .sum()
+    };
+    LEGACY_COLORS
+        .iter()
+        .min_by_key(|(_, other)| distance(other))
+        .map_or(color, |&(legacy, _)| legacy)
+}
+
+fn downgrade_style(style: &mut ContentStyle) {
+    style.foreground_color = style.foreground_color.map(legacy_color);
+    style.background_color = style.background_color.map(legacy_color);
+    style.underline_color = style.underline_color.map(legacy_color);
+}
+
+fn downgrade_char(styled_char: &mut StyledChar) {
+    let mut compound_style = styled_char.compound_style().clone();
+    downgrade_style(&mut compound_style.object_style);
+    styled_char.set_compound_style(compound_style);
+}
+
+/// Replace the colors of the skin with the nearest legacy colors
+pub(crate) fn downgrade_skin(skin: &mut MadSkin) {
+    for style in [
+        &mut skin.paragraph.compound_style,
+        &mut skin.bold,
+        &mut skin.italic,
+        &mut skin.strikeout,
+        &mut skin.inline_code,
+        &mut skin.code_block.compound_style,
+        &mut skin.table.compound_style,
+        &mut skin.ellipsis,
+    ] {
+        downgrade_style(&mut style.object_style);
+    }
+    for header in &mut skin.headers {
+        downgrade_style(&mut header.compound_style.object_style);
+    }
+    for styled_char in [
+        &mut skin.bullet,
+        &mut skin.quote_mark,
+        &mut skin.horizontal_rule,
+        &mut skin.scrollbar.track,
+        &mut skin.scrollbar.thumb,
+    ] {
+        downgrade_char(styled_char);
+    }
+    for styled_char in skin.special_chars.values_mut() {
+        downgrade_char(styled_char);
+    }
+}
@@ -41,6 +41,126 @@ The examples directory shows how to customize the help.
 
 */
 
+mod accessibility;
+mod arg_name;
+mod audit;
+mod badge;
+mod changelog;
+mod columns;
+mod conditions;
+mod crossref;
+mod decoration;
+mod deprecation;
+mod detection;
+mod direction;
+mod error;
+mod error_printer;
+mod expander_overrides;
+#[cfg(feature = "figlet")]
+mod figlet;
+mod gate;
+mod global;
+mod glossary;
+#[cfg(feature = "graphics")]
+mod graphics;
+mod help_format;
+mod help_level;
+#[cfg(feature = "history")]
+mod history;
+mod install;
+mod legacy_colors;
+mod line_style;
+mod line_values;
+mod linear;
+mod links;
+mod locale;
+mod pager;
+mod partials;
+mod preset;
 mod printer;
+mod record;
+mod section;
+mod section_order;
+mod stable_hash;
+mod style_hints;
+mod table_style;
+mod target;
+mod template_check;
+mod test_options;
+mod unknown_arg;
+mod usage_style;
+mod user_config;
+mod value_type;
+mod variables;
+mod width;
+mod width_strategy;
+#[cfg(feature = "interactive")]
+mod wizard;
+mod wrap;
 
-pub use printer::*;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+pub mod completions_meta;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod derive_support;
+pub mod diagnostics;
+pub mod diff;
+pub mod lint;
+#[cfg(feature = "interactive")]
+pub mod picker;
+pub mod suggest;
+
+#[cfg(feature = "derive")]
+pub use clap_help_derive::HelpPrinter;
+
+pub use detection::Detection;
+
+#[cfg(feature = "figlet")]
+pub use figlet::*;
+
+#[cfg(feature = "graphics")]
+pub use graphics::{detect_graphics_protocol, GraphicsProtocol, ImageSource};
+
+#[cfg(feature = "history")]
+pub use history::record_subcommand_usage;
+
+pub use {
+    accessibility::*,
+    audit::*,
+    badge::Badge,
+    changelog::*,
+    crossref::BrokenReference,
+    decoration::*,
+    direction::*,
+    error::Error,
+    error_printer::*,
+    expander_overrides::*,
+    gate::*,
+    global::{set_global_defaults, ColorChoice, GlobalConfig},
+    help_format::*,
+    help_level::*,
+    install::*,
+    line_style::LineStyle,
+    links::*,
+    locale::*,
+    partials::*,
+    preset::*,
+    printer::*,
+    record::RecordFormat,
+    section::*,
+    section_order::*,
+    style_hints::*,
+    table_style::*,
+    target::*,
+    template_check::*,
+    test_options::*,
+    unknown_arg::UnknownArg,
+    usage_style::UsageStyle,
+    user_config::options_layout,
+    value_type::*,
+    variables::*,
+    width::*,
+    width_strategy::*,
+    wrap::*,
+};
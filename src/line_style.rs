@@ -0,0 +1,186 @@
+use {
+    std::{borrow::Cow, fmt},
+    termimad::{
+        crossterm::style::Color, minimad::Compound, CompositeKind, FmtComposite, FmtLine, MadSkin,
+    },
+};
+
+/// The colors of the lines of an option, see
+/// [crate::Printer::with_option_styler]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LineStyle {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl LineStyle {
+    /// A style with only a foreground color, eg `LineStyle::fg(Color::Red)`
+    pub fn fg(color: Color) -> Self {
+        Self {
+            fg: Some(color),
+            bg: None,
+        }
+    }
+    pub fn with_bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+    /// Change the colors of the texts of the skin, but not the ones
+    /// of the table borders
+    pub(crate) fn apply_to_skin(&self, skin: &mut MadSkin) {
+        let styles = [
+            &mut skin.paragraph.compound_style,
+            &mut skin.bold,
+            &mut skin.italic,
+            &mut skin.inline_code,
+            &mut skin.strikeout,
+        ];
+        for style in styles {
+            if let Some(fg) = self.fg {
+                style.set_fg(fg);
+            }
+            if let Some(bg) = self.bg {
+                style.set_bg(bg);
+            }
+        }
+    }
+}
+
+/// Delimiter of the invisible marker put in the help of an option,
+/// so that its lines can be found in the rendered text
+const MARK: char = '\u{2063}';
+const BIT_0: char = '\u{2062}';
+const BIT_1: char = '\u{2064}';
+
+/// Delimiter of the invisible markers around the text of a badge
+pub(crate) const BADGE_MARK: char = '\u{2061}';
+
+/// Delimiter of the invisible markers around the tokens of the usage
+pub(crate) const USAGE_MARK: char = '\u{2060}';
+
+/// Return the marker of the row of the argument of the given index,
+/// made of zero width chars
+pub(crate) fn row_marker(idx: usize) -> String {
+    index_marker(MARK, idx)
+}
+
+/// Return a marker made of the delimiter and of the bits of the index
+pub(crate) fn index_marker(delimiter: char, idx: usize) -> String {
+    let mut marker = String::from(delimiter);
+    let mut idx = idx;
+    loop {
+        marker.push(if idx & 1 == 1 { BIT_1 } else { BIT_0 });
+        idx >>= 1;
+        if idx == 0 {
+            break;
+        }
+    }
+    marker.push(delimiter);
+    marker
+}
+
+/// Return the index given to the first marker of the string
+/// with this delimiter
+pub(crate) fn marker_index(s: &str, delimiter: char) -> Option<usize> {
+    let start = s.find(delimiter)? + delimiter.len_utf8();
+    let end = s[start..].find(delimiter)? + start;
+    let mut idx = 0;
+    for (bit, c) in s[start..end].chars().enumerate() {
+        if c == BIT_1 {
+            idx |= 1 << bit;
+        }
+    }
+    Some(idx)
+}
+
+fn composite_marker_index(composite: &FmtComposite<'_>) -> Option<usize> {
+    composite
+        .compounds
+        .iter()
+        .map(|compound: &Compound| compound.src)
+        .find_map(|src| marker_index(src, MARK))
+}
+
+/// Replace the texts found between a marker of the given delimiter
+/// and the closing delimiter with their restyled version, given the
+/// index of the marker. Texts which were wrapped, or which `restyle`
+/// rejects, are left as is, without their markers.
+pub(crate) fn restyle_marked(
+    rendered: &str,
+    delimiter: char,
+    restyle: impl Fn(usize, &str) -> Option<String>,
+) -> String {
+    let mut restyled = String::with_capacity(rendered.len());
+    let mut rest = rendered;
+    while let Some(start) = rest.find(delimiter) {
+        restyled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let idx = marker_index(rest, delimiter);
+        // skip the marker: the delimiter, the bits, and the delimiter
+        let after = rest[delimiter.len_utf8()..]
+            .find(delimiter)
+            .map(|end| &rest[2 * delimiter.len_utf8() + end..]);
+        let (Some(idx), Some(after)) = (idx, after) else {
+            rest = &rest[delimiter.len_utf8()..];
+            continue;
+        };
+        let Some(end) = after.find(delimiter) else {
+            rest = after;
+            continue;
+        };
+        let text = &after[..end];
+        match restyle(idx, text).filter(|_| !text.contains('\n')) {
+            Some(styled) => restyled.push_str(&styled),
+            None => restyled.push_str(text),
+        }
+        rest = &after[end + delimiter.len_utf8()..];
+    }
+    restyled.push_str(rest);
+    restyled
+}
+
+/// Return the index of the marker of the line, if any
+pub(crate) fn line_marker_index(line: &FmtLine<'_>) -> Option<usize> {
+    match line {
+        FmtLine::Normal(composite) => composite_marker_index(composite),
+        FmtLine::TableRow(row) => row.cells.iter().find_map(composite_marker_index),
+        _ => None,
+    }
+}
+
+/// Tell whether the line may be the continuation of a wrapped
+/// row or list item
+pub(crate) fn is_continuation(line: &FmtLine<'_>) -> bool {
+    match line {
+        FmtLine::TableRow(_) => true,
+        FmtLine::Normal(composite) => {
+            matches!(composite.kind, CompositeKind::ListItemFollowUp(_))
+        }
+        _ => false,
+    }
+}
+
+/// Remove the row and badge markers of a rendered text
+pub(crate) fn strip_markers(s: &str) -> Cow<'_, str> {
+    if !s.contains(MARK) && !s.contains(BADGE_MARK) && !s.contains(USAGE_MARK) {
+        return Cow::Borrowed(s);
+    }
+    Cow::Owned(
+        s.chars()
+            .filter(|c| !matches!(*c, MARK | BADGE_MARK | USAGE_MARK | BIT_0 | BIT_1))
+            .collect(),
+    )
+}
+
+/// A line displayed with a skin, as by termimad's `FmtText`
+pub(crate) struct SkinnedLine<'s, 'l> {
+    pub skin: &'s MadSkin,
+    pub line: &'s FmtLine<'l>,
+    pub width: Option<usize>,
+}
+
+impl fmt::Display for SkinnedLine<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.skin.write_fmt_line(f, self.line, self.width, false)
+    }
+}
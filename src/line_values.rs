@@ -0,0 +1,55 @@
+use {std::fmt, termimad::minimad::OwningSubTemplateExpander};
+
+/// The values of an element of a sub-loop, computed once to fill
+/// several sub-loops, eg an option being in `option-lines`,
+/// `common-option-lines` and `value-option-lines`
+pub(crate) struct LineValues {
+    /// name, value, and whether the value is markdown
+    values: Vec<(&'static str, String, bool)>,
+}
+
+impl LineValues {
+    pub fn new() -> Self {
+        Self {
+            // an option line has about 20 values
+            values: Vec::with_capacity(24),
+        }
+    }
+    /// Set a value, not interpreted as markdown
+    pub fn set(&mut self, name: &'static str, value: impl fmt::Display) {
+        self.values.push((name, value.to_string(), false));
+    }
+    /// Set a markdown value
+    pub fn set_md(&mut self, name: &'static str, value: impl Into<String>) {
+        self.values.push((name, value.into(), true));
+    }
+    /// Return a value, eg to display it out of a template
+    #[cfg(feature = "interactive")]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(n, _, _)| *n == name)
+            .map(|(_, value, _)| value.as_str())
+    }
+    /// Fill the element of a sub-loop, the values staying available
+    /// for other sub-loops
+    pub fn fill(&self, sub: &mut OwningSubTemplateExpander<'static>) {
+        for (name, value, md) in &self.values {
+            if *md {
+                sub.set_md(name, value.as_str());
+            } else {
+                sub.set(name, value);
+            }
+        }
+    }
+    /// Fill the element of a sub-loop, moving the values
+    pub fn fill_last(self, sub: &mut OwningSubTemplateExpander<'static>) {
+        for (name, value, md) in self.values {
+            if md {
+                sub.set_md(name, value);
+            } else {
+                sub.set(name, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use termimad::minimad::{Alignment, Composite, CompositeStyle, Compound, Line, Text};
+
+/// Write the text back as markdown, each table row being replaced with
+/// a paragraph where every cell is preceded by the header of its column,
+/// eg "Short: -v. Long: --verbose. Description: Verbose output."
+pub(crate) fn linear_markdown(text: &Text<'_>) -> String {
+    let mut md = String::new();
+    let mut headers: Vec<String> = Vec::new();
+    let mut rows = 0; // rows already seen in the current table
+    for (idx, line) in text.lines.iter().enumerate() {
+        if !matches!(line, Line::TableRow(_) | Line::TableRule(_)) {
+            rows = 0;
+        }
+        match line {
+            Line::TableRow(row) => {
+                rows += 1;
+                if rows == 1 {
+                    headers.clear();
+                    // a header row is separated from the other rows by a rule
+                    if matches!(text.lines.get(idx + 1), Some(Line::TableRule(_)))
+                        && matches!(text.lines.get(idx + 2), Some(Line::TableRow(_)))
+                    {
+                        headers = row.cells.iter().map(header_text).collect();
+                        continue;
+                    }
+                }
+                let parts: Vec<String> = row
+                    .cells
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, cell)| {
+                        let content = compounds_md(&cell.compounds);
+                        let content = content.trim();
+                        if content.is_empty() {
+                            return None;
+                        }
+                        Some(match headers.get(i).filter(|h| !h.is_empty()) {
+                            Some(header) => format!("{header}: {content}"),
+                            None => content.to_string(),
+                        })
+                    })
+                    .collect();
+                if parts.is_empty() {
+                    continue;
+                }
+                // without headers, the first cell is usually the name of the row
+                let mut paragraph = match (headers.is_empty(), parts.split_first()) {
+                    (true, Some((name, rest))) if !rest.is_empty() => {
+                        format!("{name}: {}", rest.join(". "))
+                    }
+                    _ => parts.join(". "),
+                };
+                if !paragraph.ends_with(['.', '!', '?']) {
+                    paragraph.push('.');
+                }
+                md.push_str(&paragraph);
+                md.push_str("\n\n");
+            }
+            Line::TableRule(_) => {}
+            Line::Normal(composite) => {
+                md.push_str(&composite_md(composite));
+                md.push('\n');
+            }
+            Line::CodeFence(_) => md.push_str("```\n"),
+            Line::HorizontalRule => md.push_str("---\n"),
+        }
+    }
+    md
+}
+
+/// Write the text back as markdown, keeping the tables, whose only
+/// rule is the one following the first row, as in GitHub markdown
+pub(crate) fn text_markdown(text: &Text<'_>) -> String {
+    let mut md = String::new();
+    let mut rows = 0; // rows already seen in the current table
+    for line in &text.lines {
+        if !matches!(line, Line::TableRow(_) | Line::TableRule(_)) {
+            rows = 0;
+        }
+        match line {
+            Line::TableRow(row) => {
+                rows += 1;
+                for cell in &row.cells {
+                    md.push('|');
+                    md.push_str(compounds_md(&cell.compounds).trim());
+                }
+                md.push_str("|\n");
+            }
+            Line::TableRule(rule) => {
+                if rows != 1 {
+                    continue;
+                }
+                for alignment in &rule.cells {
+                    md.push_str(match alignment {
+                        Alignment::Left => "|:-",
+                        Alignment::Center => "|:-:",
+                        Alignment::Right => "|-:",
+                        Alignment::Unspecified => "|-",
+                    });
+                }
+                md.push_str("|\n");
+            }
+            Line::Normal(composite) => {
+                md.push_str(&composite_md(composite));
+                md.push('\n');
+            }
+            Line::CodeFence(_) => md.push_str("```\n"),
+            Line::HorizontalRule => md.push_str("---\n"),
+        }
+    }
+    md
+}
+
+/// The unstyled text of a header cell, capitalized
+fn header_text(cell: &Composite<'_>) -> String {
+    let text: String = cell.compounds.iter().map(|c| c.src).collect();
+    let text = text.trim().trim_end_matches(':');
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn composite_md(composite: &Composite<'_>) -> String {
+    let prefix = match composite.style {
+        CompositeStyle::Paragraph => String::new(),
+        CompositeStyle::Header(level) => format!("{} ", "#".repeat(level as usize)),
+        CompositeStyle::ListItem(depth) => format!("{}* ", "  ".repeat(depth as usize)),
+        CompositeStyle::Code => "    ".to_string(),
+        CompositeStyle::Quote => "> ".to_string(),
+    };
+    let md = compounds_md(&composite.compounds);
+    if prefix.is_empty() {
+        // leading spaces, eg in a tree, would make a code line
+        let content = md.trim_start_matches(' ');
+        let indent = md.len() - content.len();
+        return "\u{a0}".repeat(indent) + content;
+    }
+    prefix + &md
+}
+
+/// Write the compounds as markdown, merging the consecutive
+/// compounds of the same style
+fn compounds_md(compounds: &[Compound<'_>]) -> String {
+    let mut md = String::new();
+    let mut idx = 0;
+    while idx < compounds.len() {
+        let style = &compounds[idx];
+        let mut src = String::new();
+        while idx < compounds.len() && same_style(style, &compounds[idx]) {
+            src.push_str(compounds[idx].src);
+            idx += 1;
+        }
+        if src.is_empty() {
+            continue;
+        }
+        let marks = [
+            (style.code, "`"),
+            (style.bold, "**"),
+            (style.italic, "*"),
+            (style.strikeout, "~~"),
+        ];
+        for (set, mark) in marks {
+            if set {
+                md.push_str(mark);
+            }
+        }
+        md.push_str(&src);
+        for (set, mark) in marks.iter().rev() {
+            if *set {
+                md.push_str(mark);
+            }
+        }
+    }
+    md
+}
+
+fn same_style(a: &Compound<'_>, b: &Compound<'_>) -> bool {
+    a.bold == b.bold && a.italic == b.italic && a.code == b.code && a.strikeout == b.strikeout
+}
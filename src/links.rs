@@ -0,0 +1,54 @@
+use {std::env, termimad::crossterm::tty::IsTty};
+
+/// Tell whether the terminal is known to render OSC 8 hyperlinks.
+///
+/// The `FORCE_HYPERLINK` environment variable can be set to `1`
+/// or `0` to override the detection.
+pub fn hyperlinks_supported() -> bool {
+    if let Ok(force) = env::var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if !std::io::stdout().is_tty() {
+        return false;
+    }
+    let var = |name| env::var(name).unwrap_or_default();
+    if var("TERM") == "dumb" {
+        return false;
+    }
+    if matches!(
+        var("TERM_PROGRAM").as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper" | "Tabby"
+    ) {
+        return true;
+    }
+    if let Ok(vte_version) = var("VTE_VERSION").parse::<u32>() {
+        return vte_version >= 5000;
+    }
+    let term = var("TERM");
+    ["kitty", "alacritty", "foot", "ghostty", "wezterm"]
+        .iter()
+        .any(|name| term.contains(name))
+        || env::var_os("KITTY_WINDOW_ID").is_some()
+        || env::var_os("WT_SESSION").is_some()
+        || env::var_os("KONSOLE_VERSION").is_some()
+}
+
+/// Wrap every occurrence of the urls in the text into an OSC 8
+/// hyperlink, trying the longest urls first
+pub(crate) fn hyperlink_urls(text: &str, urls: &[&str]) -> String {
+    let mut urls: Vec<&str> = urls.iter().copied().filter(|u| !u.is_empty()).collect();
+    urls.sort_by_key(|u| std::cmp::Reverse(u.len()));
+    let mut linked = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(url) = urls.iter().find(|&&url| rest.starts_with(url)) {
+            linked.push_str(&format!("\x1b]8;;{url}\x1b\\{url}\x1b]8;;\x1b\\"));
+            rest = &rest[url.len()..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            linked.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    linked
+}
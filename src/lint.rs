@@ -0,0 +1,181 @@
+//! Checks of the help strings of a command, to be run in a test of the
+//! application so that inconsistencies are caught by the CI.
+
+use {
+    crate::arg_name::arg_name,
+    clap::{Arg, ArgAction, Command},
+    std::fmt,
+};
+
+/// Lines of help longer than this are reported, as they're probably
+/// paragraphs where a line break was forgotten
+pub const MAX_LINE_LENGTH: usize = 100;
+
+/// The kind of a problem found in the help strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintKind {
+    /// A visible argument without help
+    MissingHelp,
+    /// A help ending with a period while most don't, or the reverse
+    InconsistentPeriod,
+    /// A help starting with a lowercase letter while most start with
+    /// an uppercase one, or the reverse
+    InconsistentCase,
+    /// A line longer than [MAX_LINE_LENGTH]
+    LongLine,
+}
+
+/// A problem found in a help string, see [check]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Lint {
+    pub kind: LintKind,
+    /// The argument (eg `--format` or `<FILE>`) or the subcommand,
+    /// prefixed with the names of the parent subcommands
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Tell whether the argument is one of the help and version flags
+/// generated by clap, whose helps aren't written by the application
+fn is_generated(arg: &Arg) -> bool {
+    matches!(
+        arg.get_action(),
+        ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+    )
+}
+
+/// Report the helps which don't follow the convention of most of the
+/// others, eg ending with a period, for a feature of the help given
+/// by `has`, when it's known
+fn check_consistency(
+    helps: &[(String, String)],
+    has: impl Fn(&str) -> Option<bool>,
+    kind: LintKind,
+    with: &str,
+    without: &str,
+    lints: &mut Vec<Lint>,
+) {
+    let features: Vec<(&String, bool)> = helps
+        .iter()
+        .filter_map(|(location, help)| has(help).map(|h| (location, h)))
+        .collect();
+    let count = features.iter().filter(|(_, h)| *h).count();
+    if count == 0 || count == features.len() {
+        return;
+    }
+    // on a tie, the first help is the reference
+    let majority = match (count * 2).cmp(&features.len()) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Less => false,
+        std::cmp::Ordering::Equal => features[0].1,
+    };
+    for (location, h) in features {
+        if h != majority {
+            lints.push(Lint {
+                kind,
+                location: location.clone(),
+                message: if h { with } else { without }.to_string(),
+            });
+        }
+    }
+}
+
+fn check_into(cmd: &Command, prefix: &str, lints: &mut Vec<Lint>) {
+    let mut helps: Vec<(String, String)> = Vec::new();
+    for arg in cmd.get_arguments() {
+        if arg.is_hide_set() || is_generated(arg) {
+            continue;
+        }
+        let location = format!("{prefix}{}", arg_name(arg));
+        match arg.get_help() {
+            Some(help) if !help.to_string().trim().is_empty() => {
+                helps.push((location, help.to_string()));
+            }
+            _ => lints.push(Lint {
+                kind: LintKind::MissingHelp,
+                location,
+                message: "no help".to_string(),
+            }),
+        }
+    }
+    for subcommand in cmd.get_subcommands().filter(|s| !s.is_hide_set()) {
+        if let Some(about) = subcommand.get_about() {
+            helps.push((
+                format!("{prefix}{}", subcommand.get_name()),
+                about.to_string(),
+            ));
+        }
+    }
+    for (location, help) in &helps {
+        for line in help.lines() {
+            let length = line.chars().count();
+            if length > MAX_LINE_LENGTH {
+                lints.push(Lint {
+                    kind: LintKind::LongLine,
+                    location: location.clone(),
+                    message: format!("line of {length} chars"),
+                });
+            }
+        }
+    }
+    check_consistency(
+        &helps,
+        |help| Some(help.trim_end().ends_with('.')),
+        LintKind::InconsistentPeriod,
+        "ends with a period, unlike most helps",
+        "doesn't end with a period, unlike most helps",
+        lints,
+    );
+    check_consistency(
+        &helps,
+        |help| {
+            help.trim_start()
+                .chars()
+                .next()
+                .filter(|c| c.is_alphabetic())
+                .map(char::is_uppercase)
+        },
+        LintKind::InconsistentCase,
+        "starts with an uppercase letter, unlike most helps",
+        "starts with a lowercase letter, unlike most helps",
+        lints,
+    );
+    for subcommand in cmd.get_subcommands().filter(|s| !s.is_hide_set()) {
+        check_into(
+            subcommand,
+            &format!("{prefix}{} ", subcommand.get_name()),
+            lints,
+        );
+    }
+}
+
+/// Check the helps of the visible arguments and subcommands of the
+/// command, recursively: missing helps, trailing periods and initial
+/// cases not consistent with the other helps of the same command,
+/// and too long lines.
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use clap_help::lint::{check, LintKind};
+///
+/// let cmd = Command::new("mytool")
+///     .arg(Arg::new("width").long("width").help("Width of the table"))
+///     .arg(Arg::new("height").long("height").help("Height of the table"))
+///     .arg(Arg::new("color").long("color").help("use colors."))
+///     .arg(Arg::new("quiet").long("quiet"));
+/// let lints = check(&cmd);
+/// assert_eq!(lints.len(), 3);
+/// assert!(lints.iter().all(|lint| lint.location != "--width"));
+/// assert!(lints.iter().any(|lint| lint.kind == LintKind::MissingHelp));
+/// ```
+pub fn check(cmd: &Command) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    check_into(cmd, "", &mut lints);
+    lints
+}
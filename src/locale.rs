@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+/// The labels used by the default templates, as `${key}` variables,
+/// with their English texts
+pub static LABELS: &[(&str, &str)] = &[
+    ("by-label", "by"),
+    ("usage-label", "Usage:"),
+    ("options-label", "Options:"),
+    ("flags-label", "Flags:"),
+    ("common-options-label", "Common options:"),
+    ("advanced-options-label", "Advanced options:"),
+    ("subcommands-label", "Subcommands:"),
+    ("other-commands-label", "Other commands"),
+    ("most-used-label", "most used"),
+    ("new-since-label", "New since"),
+    ("keybindings-label", "Key bindings:"),
+    ("environment-label", "Environment:"),
+    ("files-label", "Files:"),
+    ("glossary-label", "Glossary:"),
+    ("tips-label", "Tips:"),
+    ("combine-flags-tip", "Short flags may be combined:"),
+    ("exit-codes-label", "Exit codes:"),
+    ("see-also-label", "See also:"),
+    ("contents-label", "Contents:"),
+    ("error-label", "Error:"),
+    ("unknown-subcommand-label", "unrecognized subcommand"),
+    ("did-you-mean-label", "Did you mean:"),
+    ("short-label", "short"),
+    ("long-label", "long"),
+    ("value-label", "value"),
+    ("name-label", "name"),
+    ("description-label", "description"),
+    ("keys-label", "keys"),
+    ("action-label", "action"),
+    ("variable-label", "variable"),
+    ("option-label", "option"),
+    ("options-plural-label", "options"),
+    ("subcommand-label", "subcommand"),
+    ("subcommands-plural-label", "subcommands"),
+    ("positional-label", "argument"),
+    ("positionals-plural-label", "arguments"),
+    ("current-value-label", "current value"),
+    ("default-label", "Default:"),
+    ("possible-values-label", "Possible values:"),
+    ("range-label", "Range:"),
+    ("and-label", "and"),
+    ("more-label", "more"),
+    ("more-options-label", "more options"),
+    ("current-label", "Current:"),
+    ("command-line-source-label", "from the command line"),
+    ("env-source-label", "from the environment"),
+    ("default-source-label", "default"),
+    ("deprecated-label", "Deprecated:"),
+    ("badges-label", "Badges:"),
+    ("requires-feature-label", "requires feature"),
+    ("only-on-label", "only on"),
+    ("required-label", "required"),
+    ("repeat-label", "may be repeated"),
+    ("levels-label", "Levels:"),
+    ("plugins-label", "Additional plugins:"),
+    (
+        "plugins-on-path-label",
+        "programs on the PATH are available as subcommands",
+    ),
+    ("raw-last-label", "after `--`"),
+    (
+        "raw-trailing-label",
+        "all the remaining arguments, even starting with `-`",
+    ),
+    ("raw-hyphen-label", "may start with `-`"),
+    ("added-label", "Added:"),
+    ("removed-label", "Removed:"),
+    ("changed-label", "Changed:"),
+    ("enable-prompt-label", "enable? [y/N]"),
+    ("value-prompt-label", "value"),
+    ("skip-hint-label", "enter to skip"),
+    ("command-line-label", "Command line:"),
+];
+
+/// The texts of the labels of the templates (eg "Options:"), so that
+/// the help of a non-English application can be translated without
+/// changing all templates.
+///
+/// ```
+/// let locale = clap_help::Locale::english()
+///     .with("options-label", "Options :")
+///     .with("usage-label", "Utilisation :")
+///     .with("default-label", "Par défaut :");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    labels: HashMap<&'static str, String>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl Locale {
+    /// The English labels
+    pub fn english() -> Self {
+        let labels = LABELS
+            .iter()
+            .map(|&(key, text)| (key, text.to_string()))
+            .collect();
+        Self { labels }
+    }
+
+    /// Change or add a label
+    pub fn set(&mut self, key: &'static str, text: impl Into<String>) {
+        self.labels.insert(key, text.into());
+    }
+
+    /// Change or add a label
+    pub fn with(mut self, key: &'static str, text: impl Into<String>) -> Self {
+        self.set(key, text);
+        self
+    }
+
+    /// Return the text of a label
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.labels.get(key).map(String::as_str)
+    }
+
+    /// Iterate over the labels, as (key, text)
+    pub fn labels(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.labels.iter().map(|(&key, text)| (key, text.as_str()))
+    }
+
+    /// Build a locale from the content of a Fluent (ftl) file whose
+    /// message ids are the keys of the [LABELS], eg
+    ///
+    /// ```ftl
+    /// options-label = Optionen:
+    /// usage-label = Verwendung:
+    /// ```
+    ///
+    /// Missing labels are kept in English, and other messages are ignored.
+    #[cfg(feature = "i18n")]
+    pub fn from_fluent(ftl: &str) -> Result<Self, fluent_syntax::parser::ParserError> {
+        use fluent_syntax::{
+            ast::{Entry, Expression, InlineExpression, PatternElement},
+            parser,
+        };
+        let resource = parser::parse(ftl).map_err(|(_, mut errors)| errors.remove(0))?;
+        let mut locale = Self::english();
+        for entry in resource.body {
+            let Entry::Message(message) = entry else {
+                continue;
+            };
+            let Some(&(key, _)) = LABELS.iter().find(|(key, _)| *key == message.id.name) else {
+                continue;
+            };
+            let Some(pattern) = message.value else {
+                continue;
+            };
+            let mut text = String::new();
+            for element in pattern.elements {
+                match element {
+                    PatternElement::TextElement { value } => text.push_str(value),
+                    PatternElement::Placeable {
+                        expression: Expression::Inline(InlineExpression::StringLiteral { value }),
+                    } => text.push_str(value),
+                    PatternElement::Placeable { .. } => {}
+                }
+            }
+            locale.set(key, text);
+        }
+        Ok(locale)
+    }
+}
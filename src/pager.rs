@@ -0,0 +1,28 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+/// Show a rendered text in the pager of the user, given by `$PAGER`,
+/// or in `less -R`, and wait for the pager to be closed.
+///
+/// An error is returned when the pager can't be started, so that the
+/// text can be printed instead.
+pub(crate) fn page(text: &str) -> io::Result<()> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // the user may quit the pager before the end of the text
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
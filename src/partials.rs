@@ -0,0 +1,102 @@
+use {crate::template_check::is_valid_name_char, std::borrow::Cow};
+
+/// The partials predefined by the printer, usable in all templates
+/// with `${>name}` and redefinable with [crate::Printer::define_partial]
+pub static PARTIALS: &[(&str, &str)] = &[
+    (
+        "option-description",
+        "${help}${required}${repeat-hint}${possible_values}${count-levels}${details-constraints}${default}${current}${deprecated-note}${gate-note}",
+    ),
+    (
+        "option-row",
+        "|${short}|${long}|${value-styled}|${>option-description}|",
+    ),
+];
+
+/// Maximum depth of partials included in partials, which stops
+/// the expansion of recursive definitions
+const MAX_DEPTH: usize = 8;
+
+/// Return the names of the `${>name}` partial references of a line
+pub(crate) fn partial_references(line: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("${>") {
+        rest = &rest[start + 3..];
+        let end = rest.find(|c| !is_valid_name_char(c)).unwrap_or(rest.len());
+        if end > 0 && rest[end..].starts_with('}') {
+            names.push(&rest[..end]);
+            rest = &rest[end + 1..];
+        }
+    }
+    names
+}
+
+/// Replace the `${>name}` references of the template with the
+/// fragments returned by the `partial` function.
+///
+/// References to unknown partials are left as is.
+pub(crate) fn expand_partials<'s, 'p>(
+    template: &'s str,
+    partial: impl Fn(&str) -> Option<&'p str>,
+) -> Cow<'s, str> {
+    if !template.contains("${>") {
+        return Cow::Borrowed(template);
+    }
+    let mut expanded = String::with_capacity(template.len());
+    expand_into(template, &partial, 0, &mut expanded);
+    Cow::Owned(expanded)
+}
+
+fn expand_into<'p>(
+    template: &str,
+    partial: &impl Fn(&str) -> Option<&'p str>,
+    depth: usize,
+    expanded: &mut String,
+) {
+    let mut rest = template;
+    while let Some(start) = rest.find("${>") {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 3..];
+        let end = after
+            .find(|c| !is_valid_name_char(c))
+            .unwrap_or(after.len());
+        let fragment = Some(&after[..end])
+            .filter(|_| end > 0 && after[end..].starts_with('}') && depth < MAX_DEPTH)
+            .and_then(partial);
+        match fragment {
+            Some(fragment) => {
+                expand_into(fragment, partial, depth + 1, expanded);
+                rest = &after[end + 1..];
+            }
+            None => {
+                expanded.push_str("${>");
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_nested_partials() {
+        let partial = |name: &str| match name {
+            "outer" => Some("<${>inner}>"),
+            "inner" => Some("in"),
+            _ => None,
+        };
+        assert_eq!(expand_partials("a ${>outer} b", partial), "a <in> b");
+        assert_eq!(expand_partials("${>unknown}", partial), "${>unknown}");
+        assert!(matches!(expand_partials("${x}", partial), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn stop_recursive_partials() {
+        let expanded = expand_partials("${>x}", |_| Some("x${>x}"));
+        assert_eq!(expanded, format!("{}${{>x}}", "x".repeat(MAX_DEPTH)));
+    }
+}
@@ -0,0 +1,203 @@
+//! A fuzzy searchable list of the subcommands, for applications
+//! which let the user pick one when invoked without argument.
+
+use {
+    crate::Printer,
+    clap::Command,
+    std::io::{self, Write},
+    termimad::{
+        crossterm::{
+            cursor,
+            event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+            queue,
+            style::{Attribute, Print, SetAttribute},
+            terminal::{self, ClearType},
+            tty::IsTty,
+        },
+        MadSkin,
+    },
+};
+
+/// An item proposed by the picker, eg a subcommand
+pub(crate) struct Choice {
+    pub name: String,
+    pub about: String,
+}
+
+/// Return the score of the pattern in the text, when all its chars
+/// are found in order, case insensitively. The lower the better:
+/// consecutive chars and a match at the start are favored.
+fn fuzzy_score(pattern: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut previous: Option<usize> = None;
+    for pc in pattern.to_lowercase().chars() {
+        let found = text[pos..].iter().position(|&tc| tc == pc)? + pos;
+        score += match previous {
+            Some(previous) => found - previous - 1,
+            None => found,
+        };
+        previous = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+/// Return the indexes of the choices matching the pattern, the best
+/// ones first, the names being favored over the about texts
+fn filter(choices: &[Choice], pattern: &str) -> Vec<usize> {
+    let mut scored: Vec<(usize, usize)> = choices
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, choice)| {
+            let score = fuzzy_score(pattern, &choice.name)
+                .or_else(|| fuzzy_score(pattern, &choice.about).map(|score| score + 1000))?;
+            Some((score, idx))
+        })
+        .collect();
+    scored.sort();
+    scored.into_iter().map(|(_, idx)| idx).collect()
+}
+
+/// Restores the terminal when dropped, even on error
+struct RawScreen;
+
+impl RawScreen {
+    fn enter(w: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        queue!(w, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawScreen {
+    fn drop(&mut self) {
+        let mut w = io::stderr();
+        let _ = queue!(w, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = w.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn draw(
+    w: &mut impl Write,
+    skin: &MadSkin,
+    title: &str,
+    pattern: &str,
+    choices: &[Choice],
+    matching: &[usize],
+    selection: usize,
+) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    queue!(w, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+    queue!(w, Print(skin.inline(&format!("**{title}** `{pattern}`"))))?;
+    let rows = (height as usize).saturating_sub(2);
+    // the list scrolls so that the selection stays visible
+    let top = selection.saturating_sub(rows.saturating_sub(1));
+    for (i, &idx) in matching.iter().enumerate().skip(top).take(rows) {
+        queue!(w, cursor::MoveTo(0, (i - top + 2) as u16))?;
+        let choice = &choices[idx];
+        let line = format!("**{}** {}", choice.name, choice.about);
+        let line: String = line.chars().take(width as usize).collect();
+        if i == selection {
+            queue!(w, SetAttribute(Attribute::Reverse))?;
+        }
+        queue!(w, Print(skin.inline(&line)), SetAttribute(Attribute::Reset))?;
+    }
+    w.flush()
+}
+
+/// Let the user pick an item in a list filtered as they type, and
+/// return its index, or `None` when they quit with `esc`, or when
+/// the terminal isn't interactive.
+///
+/// Up and down arrows move the selection, enter validates it.
+pub(crate) fn pick(title: &str, choices: &[Choice], skin: &MadSkin) -> Option<usize> {
+    if choices.is_empty() || !io::stdin().is_tty() || !io::stderr().is_tty() {
+        return None;
+    }
+    let mut w = io::stderr();
+    let _screen = RawScreen::enter(&mut w).ok()?;
+    let mut pattern = String::new();
+    let mut selection = 0;
+    loop {
+        let matching = filter(choices, &pattern);
+        selection = selection.min(matching.len().saturating_sub(1));
+        draw(&mut w, skin, title, &pattern, choices, &matching, selection).ok()?;
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read().ok()?
+        else {
+            continue;
+        };
+        match code {
+            KeyCode::Esc => return None,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return None,
+            KeyCode::Enter => {
+                if let Some(&idx) = matching.get(selection) {
+                    return Some(idx);
+                }
+            }
+            KeyCode::Up => selection = selection.saturating_sub(1),
+            KeyCode::Down => selection += 1,
+            KeyCode::Backspace => {
+                pattern.pop();
+                selection = 0;
+            }
+            KeyCode::Char(c) => {
+                pattern.push(c);
+                selection = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Let the user pick a subcommand in a list filtered as they type,
+/// with the skin, and return its name, or `None` when they quit
+/// with `esc`, or when the terminal isn't interactive.
+///
+/// Up and down arrows move the selection, enter validates it.
+pub fn pick_subcommand_with_skin(cmd: &Command, skin: &MadSkin) -> Option<String> {
+    let choices: Vec<Choice> = cmd
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set())
+        .map(|subcommand| Choice {
+            name: subcommand.get_name().to_string(),
+            about: subcommand
+                .get_about()
+                .map(|about| about.to_string())
+                .unwrap_or_default(),
+        })
+        .collect();
+    let title = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+    let idx = pick(title, &choices, skin)?;
+    choices.into_iter().nth(idx).map(|choice| choice.name)
+}
+
+/// Let the user pick a subcommand in a list filtered as they type,
+/// with a skin fitting the terminal's theme.
+///
+/// ```no_run
+/// # use clap::{CommandFactory, Parser, Subcommand};
+/// # #[derive(Parser)]
+/// # struct Args {
+/// #     #[command(subcommand)]
+/// #     command: Option<Commands>,
+/// # }
+/// # #[derive(Subcommand)]
+/// # enum Commands { Build, Run }
+/// let args = Args::parse();
+/// if args.command.is_none() {
+///     if let Some(name) = clap_help::picker::pick_subcommand(&Args::command()) {
+///         println!("running {name}");
+///     }
+/// }
+/// ```
+pub fn pick_subcommand(cmd: &Command) -> Option<String> {
+    pick_subcommand_with_skin(cmd, &Printer::make_skin())
+}
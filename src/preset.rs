@@ -0,0 +1,216 @@
+use {
+    crate::{
+        detection::{terminal_luma, terminal_luma_with, Theme},
+        target::print_md,
+        Detection, Printer, Rgb, SkinAudit,
+    },
+    clap::Command,
+    termimad::{
+        ansi, crossterm::style::Attribute, CompoundStyle, MadSkin, ROUNDED_TABLE_BORDER_CHARS,
+    },
+};
+
+/// A named skin, which can be given to [Printer::with_preset]
+/// or chosen by the user, eg with a `--theme` argument
+#[derive(Debug, Clone, Copy)]
+pub struct StylePreset {
+    pub name: &'static str,
+    make_skin: SkinMaker,
+}
+
+/// How the skin of a preset is built
+#[derive(Debug, Clone, Copy)]
+enum SkinMaker {
+    /// Independently of the terminal
+    Fixed(fn() -> MadSkin),
+    /// For the luma of the background of the terminal, if known
+    Themed(fn(Option<f32>) -> MadSkin),
+}
+
+/// The registered presets, starting with the default one
+pub static STYLE_PRESETS: &[StylePreset] = PRESETS;
+
+// a const, so that the derive can check its `theme` setting at compile time
+pub(crate) const PRESETS: &[StylePreset] = &[
+    StylePreset::themed("auto", auto_skin),
+    StylePreset::new("dark", MadSkin::default_dark),
+    StylePreset::new("light", MadSkin::default_light),
+    StylePreset::new("sunset", sunset_skin),
+    StylePreset::themed("high-contrast", high_contrast_skin),
+    StylePreset::themed("deuteranopia", deuteranopia_skin),
+    StylePreset::themed("protanopia", protanopia_skin),
+    StylePreset::themed("tritanopia", tritanopia_skin),
+    StylePreset::themed("ascii", ascii_skin),
+    StylePreset::new("plain", MadSkin::no_style),
+];
+
+impl StylePreset {
+    pub const fn new(name: &'static str, make_skin: fn() -> MadSkin) -> Self {
+        Self {
+            name,
+            make_skin: SkinMaker::Fixed(make_skin),
+        }
+    }
+
+    /// A preset whose skin depends on the background of the terminal
+    const fn themed(name: &'static str, make_skin: fn(Option<f32>) -> MadSkin) -> Self {
+        Self {
+            name,
+            make_skin: SkinMaker::Themed(make_skin),
+        }
+    }
+
+    /// Find a registered preset by its name
+    pub fn find(name: &str) -> Option<&'static StylePreset> {
+        STYLE_PRESETS.iter().find(|preset| preset.name == name)
+    }
+
+    /// Build the skin of the preset
+    pub fn skin(&self) -> MadSkin {
+        match self.make_skin {
+            SkinMaker::Fixed(make_skin) => make_skin(),
+            SkinMaker::Themed(make_skin) => make_skin(terminal_luma()),
+        }
+    }
+
+    /// Build the skin of the preset for the terminal the help is
+    /// written to, queried according to the detection policy
+    pub(crate) fn skin_for(&self, detection: Detection, output_is_tty: bool) -> MadSkin {
+        match self.make_skin {
+            SkinMaker::Fixed(make_skin) => make_skin(),
+            SkinMaker::Themed(make_skin) => make_skin(terminal_luma_with(detection, output_is_tty)),
+        }
+    }
+
+    /// Build the skin of the preset for a background of the given luma,
+    /// without querying the terminal
+    fn skin_on(&self, luma: f32) -> MadSkin {
+        match self.make_skin {
+            SkinMaker::Fixed(make_skin) => make_skin(),
+            SkinMaker::Themed(make_skin) => make_skin(Some(luma)),
+        }
+    }
+
+    /// Give the contrasts of the elements of the skin made for the
+    /// background, on this background. The terminal isn't queried.
+    ///
+    /// ```
+    /// use clap_help::{Rgb, StylePreset};
+    ///
+    /// let preset = StylePreset::find("auto").unwrap();
+    /// let on_white = preset.contrast_report(Rgb::new(255, 255, 255));
+    /// let on_black = preset.contrast_report(Rgb::new(0, 0, 0));
+    /// assert_ne!(on_white.elements, on_black.elements);
+    /// ```
+    pub fn contrast_report(&self, background: Rgb) -> SkinAudit {
+        SkinAudit::check_on(&self.skin_on(background.luma()), background)
+    }
+}
+
+/// The default skin of the theme of the terminal
+pub(crate) fn auto_skin(luma: Option<f32>) -> MadSkin {
+    match Theme::of_luma(luma) {
+        Theme::Light => MadSkin::default_light(),
+        Theme::Dark => MadSkin::default_dark(),
+        Theme::Other => MadSkin::default(),
+    }
+}
+
+fn sunset_skin() -> MadSkin {
+    let mut skin = MadSkin::default();
+    skin.headers[0].compound_style.set_fg(ansi(202));
+    skin.bold.set_fg(ansi(202));
+    skin.italic = CompoundStyle::with_fg(ansi(45));
+    skin.inline_code = CompoundStyle::with_fg(ansi(223));
+    skin.table_border_chars = ROUNDED_TABLE_BORDER_CHARS;
+    skin
+}
+
+/// A skin with only black, white, and saturated colors, for low-vision
+/// users, made for the background of the terminal
+fn high_contrast_skin(luma: Option<f32>) -> MadSkin {
+    let light = luma.is_some_and(|luma| luma > 0.6);
+    let (text, emphasis, secondary, border) = if light {
+        (ansi(16), ansi(18), ansi(88), ansi(16))
+    } else {
+        (ansi(231), ansi(226), ansi(51), ansi(231))
+    };
+    let mut skin = MadSkin::no_style();
+    skin.set_fg(text);
+    skin.bold.set_fg(emphasis);
+    skin.bold.add_attr(Attribute::Bold);
+    skin.italic.set_fg(secondary);
+    skin.italic.add_attr(Attribute::Italic);
+    skin.strikeout.add_attr(Attribute::CrossedOut);
+    skin.inline_code.set_fg(secondary);
+    skin.code_block.set_fg(secondary);
+    for header in &mut skin.headers {
+        header.add_attr(Attribute::Bold);
+        header.set_fg(emphasis);
+    }
+    skin.headers[0].add_attr(Attribute::Underlined);
+    skin.table.set_fg(border);
+    skin.bullet.set_fg(emphasis);
+    skin
+}
+
+/// A skin of the detected theme whose colors are only the given ones
+fn colorblind_skin(luma: Option<f32>, emphasis: u8, secondary: u8, code: u8) -> MadSkin {
+    let mut skin = auto_skin(luma);
+    skin.headers[0].compound_style.set_fg(ansi(emphasis));
+    skin.bold.set_fg(ansi(emphasis));
+    skin.italic.set_fg(ansi(secondary));
+    skin.inline_code = CompoundStyle::with_fg(ansi(code));
+    skin
+}
+
+/// Blue and orange, distinguishable without green cones
+fn deuteranopia_skin(luma: Option<f32>) -> MadSkin {
+    colorblind_skin(luma, 214, 39, 153)
+}
+
+/// Blue and yellow, as reds look dark without red cones
+fn protanopia_skin(luma: Option<f32>) -> MadSkin {
+    colorblind_skin(luma, 220, 33, 153)
+}
+
+/// Red and cyan, distinguishable without blue cones
+fn tritanopia_skin(luma: Option<f32>) -> MadSkin {
+    colorblind_skin(luma, 203, 44, 218)
+}
+
+fn ascii_skin(luma: Option<f32>) -> MadSkin {
+    let mut skin = auto_skin(luma);
+    skin.limit_to_ascii();
+    skin
+}
+
+/// Print a short sample of the help of the command (its title, usage,
+/// and options) in every registered preset, after the name of the preset,
+/// so that the user can choose a theme visually.
+///
+/// ```no_run
+/// # use clap::{CommandFactory, Parser};
+/// #[derive(Parser)]
+/// struct Args {
+///     /// Print the help in all themes
+///     #[arg(long)]
+///     help_themes: bool,
+/// }
+///
+/// if Args::parse().help_themes {
+///     clap_help::preview_presets(Args::command());
+/// }
+/// ```
+pub fn preview_presets(cmd: Command) {
+    for preset in STYLE_PRESETS {
+        let skin = preset.skin();
+        print_md(&skin, &format!("\n## {}", preset.name));
+        let mut printer = Printer::new(cmd.clone()).with_skin(skin);
+        *printer.template_keys_mut() = vec!["title", "usage", "options"];
+        let printed = printer.try_print_help();
+        if printed.is_err_and(|e| e.is_broken_pipe()) {
+            break;
+        }
+    }
+}
@@ -1,80 +1,557 @@
 use {
-    clap::{ArgAction, Command},
-    std::collections::HashMap,
+    crate::{
+        badge::color_badges,
+        columns::{align_columns, remove_columns},
+        conditions::{apply_conditions, BUILTIN_CONDITIONS},
+        crossref::{check_references, resolve_references},
+        decoration::decorate,
+        deprecation::parse_deprecated_help,
+        detection::{terminal_luma_with, terminal_width},
+        global::global_defaults,
+        glossary::italicize_terms,
+        help_format::{command_json, strip_escape_sequences},
+        legacy_colors::{downgrade_skin, legacy_colors_needed},
+        line_style::{is_continuation, line_marker_index, row_marker, strip_markers, SkinnedLine},
+        line_values::LineValues,
+        linear::{linear_markdown, text_markdown},
+        pager::page,
+        partials::expand_partials,
+        preset::auto_skin,
+        stable_hash::StableHasher,
+        style_hints::apply_style_hints,
+        target::Output,
+        usage_style::{style_usage, UsageLine, UsageToken},
+        user_config::{options_layout, read_user_config},
+        value_type::{format_range, value_range},
+        variables::OPTION_SUB_LOOPS,
+        *,
+    },
+    clap::{builder::Str, parser::ValueSource, Arg, ArgAction, ArgMatches, Command},
+    std::{
+        borrow::Cow,
+        collections::HashMap,
+        ffi::OsStr,
+        fs,
+        hash::{Hash, Hasher},
+        io::{self, Write},
+        ops::RangeBounds,
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError},
+    },
     termimad::{
-        minimad::{OwningTemplateExpander, TextTemplate},
-        FmtText, MadSkin,
+        minimad::{OwningTemplateExpander, Text, TextTemplate},
+        Alignment, FmtText, MadSkin,
     },
+    unicode_width::UnicodeWidthStr,
 };
 
+/// Default template for the "banner" section, present when a banner
+/// is set with [Printer::with_banner]
+pub static TEMPLATE_BANNER: &str = "
+${banner-lines
+**${line}**
+}
+";
+
 /// Default template for the "title" section
 pub static TEMPLATE_TITLE: &str = "# **${name}** ${version}";
 
 /// Default template for the "author" section
 pub static TEMPLATE_AUTHOR: &str = "
-*by* ${author}
+*${by-label}* ${author}
+";
+
+/// Template for the "summary" section, giving the numbers of options,
+/// subcommands and positional arguments, eg "14 options, 3 subcommands".
+///
+/// It's not displayed by default, use `.with("summary", TEMPLATE_SUMMARY)`
+pub static TEMPLATE_SUMMARY: &str = "
+*${summary}*
+";
+
+/// Default template for the "before-help" section, present when the
+/// command has a `before_help` or a `before_long_help`, the long one
+/// being displayed unless the level is [HelpLevel::Short]
+pub static TEMPLATE_BEFORE_HELP: &str = "
+${before-help-lines
+${line}
+}
+";
+
+/// Template for the "toc" section, enabled with [Printer::with_toc],
+/// listing the sections of the help, and the subcommands when
+/// printed by [Printer::print_help_all]
+pub static TEMPLATE_TOC: &str = "
+**${contents-label}**
+${toc-lines
+* ${title}
+}
+${?help-all
+${toc-subcommand-lines
+* ${title}
+}
+}
+";
+
+/// The "toc" template of the markdown export, linking to the sections
+static TEMPLATE_TOC_LINKED: &str = "
+**${contents-label}**
+${toc-lines
+* [${title}](#${anchor})
+}
 ";
 
 /// Default template for the "usage" section
 pub static TEMPLATE_USAGE: &str = "
-**Usage: ** `${name} [options]${positional-args}`
+**${usage-label} ** `${name} [options]${usage-groups}${positional-args}`
+";
+
+/// Template for the "usage" section with its tokens styled
+/// by the [UsageStyle] of the printer, set by [Printer::usage_style_mut]
+pub static TEMPLATE_USAGE_STYLED: &str = "
+**${usage-label} ** ${usage-styled}
 ";
 
 /// Default template for the "positionals" section
 pub static TEMPLATE_POSITIONALS: &str = "
+${?has-positionals
 ${positional-lines
-* `${key}` : ${help}
+* `${key}` : ${help}${required}${details-raw}${default}${possible_values}${gate-note}
+}
 }
 ";
 
 /// Default template for the "options" section
 pub static TEMPLATE_OPTIONS: &str = "
-**Options:**
+${?has-options
+**${options-label}**
 |:-:|:-:|:-:|:-|
-|short|long|value|description|
+|${short-label}|${long-label}|${value-label}|${description-label}|
 |:-:|:-|:-:|:-|
 ${option-lines
-|${short}|${long}|${value}|${help}${possible_values}${default}|
+${>option-row}
 }
 |-
+${more-options-lines
+*… ${count} ${more-options-label}*
+}
+}
+${?has-badges
+*${badges-label}* ${badge-legend}
+}
 ";
 
 /// Default template for the "subcommands" section
 pub static TEMPLATE_SUBCOMMANDS: &str = "
-**Subcommands:**
+${?has-subcommands
+**${subcommands-label}**
+|:-|:-|
+|${name-label}|${description-label}|
+|:-|:-|
+${subcommand-lines
+|**${name}**|${help}${most-used}|
+}
+|-
+}
+";
+
+/// A template for the "subcommands" section where the subcommands are
+/// listed under the categories given with [Printer::categorize_subcommand],
+/// eg "Build commands" and "Manifest commands"
+pub static TEMPLATE_SUBCOMMANDS_GROUPED: &str = "
+${?has-subcommands
+**${subcommands-label}**
 |:-|:-|
-|name|description|
+|${name-label}|${description-label}|
 |:-|:-|
+${subcommand-group-lines
+|${category}${name-styled}|${help}|
+}
+|-
+}
+";
+
+/// A template for the "subcommands" section where every subcommand
+/// is given with its usage, for tools whose subcommands take very
+/// different arguments
+pub static TEMPLATE_SUBCOMMANDS_DETAILED: &str = "
+${?has-subcommands
+**${subcommands-label}**
 ${subcommand-lines
-|**${name}**|${help}|
+* **${name}** : ${help}${most-used}
+  `${sub-usage}`
+}
+}
+";
+
+/// Default template for the "subcommand-tree" section, showing the
+/// nested subcommands, present when enabled with [Printer::with_subcommand_tree]
+pub static TEMPLATE_SUBCOMMAND_TREE: &str = "
+**${subcommands-label}**
+${subcommand-tree-lines
+${branch}**${name}** : ${help}
+}
+";
+
+/// Default template for the "changelog" section, present when
+/// entries are given to [Printer::with_changelog_since]
+pub static TEMPLATE_CHANGELOG: &str = "
+**${new-since-label} ${changelog-since}**
+|:-:|:-|:-|
+${changelog-lines
+|${version}|**${name}**|${description}|
+}
+|-
+";
+
+/// Default template for the "links" section, present when links
+/// are set with [Printer::with_link]
+pub static TEMPLATE_LINKS: &str = "
+**${see-also-label}**
+${link-lines
+* ${label}: ${url}
+}
+";
+
+/// Default template for the "external-subcommands" section, telling
+/// that the command accepts subcommands implemented by other programs
+pub static TEMPLATE_EXTERNAL_SUBCOMMANDS: &str = "
+${external-subcommands-note}
+";
+
+/// Default template for the "environment" section, listing the
+/// environment variables read by the arguments
+pub static TEMPLATE_ENVIRONMENT: &str = "
+${?has-environment
+**${environment-label}**
+|:-|:-|:-|:-|
+|${variable-label}|${option-label}|${description-label}|${current-value-label}|
+|:-|:-|:-|:-|
+${env-lines
+|`${var}`|${flag}|${help}|${value}|
+}
+|-
+}
+";
+
+/// Default template for the "keybindings" section, present when
+/// keyboard shortcuts are documented with [Printer::with_keybinding]
+pub static TEMPLATE_KEYBINDINGS: &str = "
+**${keybindings-label}**
+|:-:|:-|:-|
+|**${keys-label}**|**${action-label}**|**${description-label}**|
+|:-:|:-|:-|
+${keybinding-lines
+|${keys}|${action}|${description}|
+}
+|-
+";
+
+/// Default template for the "files" section, present when files
+/// are listed with [Printer::with_file]
+pub static TEMPLATE_FILES: &str = "
+**${files-label}**
+|:-|:-|
+${file-lines
+|`${path}`|${description}|
+}
+|-
+";
+
+/// Default template for the "glossary" section, present when terms
+/// are defined with [Printer::with_glossary_term]
+pub static TEMPLATE_GLOSSARY: &str = "
+**${glossary-label}**
+${glossary-lines
+* **${term}** : ${definition}
+}
+";
+
+/// Template of the "tips" section, enabled with [Printer::with_tips]
+/// or [Printer::with_tip], listing generated tips, eg telling short
+/// flags may be combined, and the ones of the application
+pub static TEMPLATE_TIPS: &str = "
+${?has-tips
+**${tips-label}**
+${tips-lines
+* ${tip}
+}
+}
+";
+
+/// Template of the "possible-values" appendix, listing all the possible
+/// values of the options, see [Printer::with_possible_values_appendix]
+pub static TEMPLATE_POSSIBLE_VALUES: &str = "
+${?has-possible-values
+**${possible-values-label}**
+|:-|:-:|:-|
+|${option-label}|${value-label}|${description-label}|
+|:-|:-:|:-|
+${possible-value-lines
+|${option}|${value}|${help}|
+}
+|-
+}
+";
+
+/// Default template for the "exit-codes" section, present when exit
+/// codes are documented with [Printer::with_exit_code]
+pub static TEMPLATE_EXIT_CODES: &str = "
+**${exit-codes-label}**
+|:-:|:-|
+${exit-code-lines
+|${code}|${meaning}|
 }
 |-
 ";
 
+/// Default template for the "after-help" section, present when the
+/// command has an `after_help` or an `after_long_help`, the long one
+/// being displayed unless the level is [HelpLevel::Short]
+pub static TEMPLATE_AFTER_HELP: &str = "
+${after-help-lines
+${line}
+}
+";
+
+/// Default template for the error banner of [Printer::print_usage_error]
+pub static TEMPLATE_ERROR: &str = "
+**${error-label}** ${error}
+";
+
 /// a template for the "options" section with the value merged to short and long
 pub static TEMPLATE_OPTIONS_MERGED_VALUE: &str = "
-**Options:**
+${?has-options
+**${options-label}**
 |:-:|:-:|:-|
-|short|long|description|
+|${short-label}|${long-label}|${description-label}|
 |:-:|:-|:-|
 ${option-lines
-|${short} *${value-short-braced}*|${long} *${value-long-braced}*|${help}${possible_values}${default}|
+|${short} *${value-short-braced}*|${long} *${value-long-braced}*|${>option-description}|
+}
+|-
+${more-options-lines
+*… ${count} ${more-options-label}*
+}
+}
+${?has-badges
+*${badges-label}* ${badge-legend}
+}
+";
+
+/// a template for the "options" section as a list, which may be easier
+/// to read than a table on narrow terminals
+pub static TEMPLATE_OPTIONS_LIST: &str = "
+${?has-options
+**${options-label}**
+${option-lines
+* **${short}** **${long}** *${value-braced}* : ${>option-description}
+}
+${more-options-lines
+*… ${count} ${more-options-label}*
+}
+}
+${?has-badges
+*${badges-label}* ${badge-legend}
+}
+";
+
+/// a template for the "options" section as a list, followed by a table
+/// of the possible values of the options, each one with its description
+pub static TEMPLATE_OPTIONS_VERBOSE: &str = "
+${?has-options
+**${options-label}**
+${option-lines
+* **${short}** **${long}** *${value-braced}* : ${help}${required}${repeat-hint}${count-levels}${details-constraints}${default}${current}${deprecated-note}${gate-note}
+}
+${more-options-lines
+*… ${count} ${more-options-label}*
+}
+}
+${?has-possible-values
+**${possible-values-label}**
+|:-|:-:|:-|
+|${option-label}|${value-label}|${description-label}|
+|:-|:-:|:-|
+${possible-value-lines
+|${option}|${value}|${help}|
+}
+|-
+}
+${?has-badges
+*${badges-label}* ${badge-legend}
+}
+";
+
+/// a template for the "options" section where the flags (the options
+/// which don't take a value) are flowed into two columns, which makes
+/// the help much shorter for tools with many flags
+pub static TEMPLATE_OPTIONS_TWO_COLUMN: &str = "
+${?has-flags
+**${flags-label}**
+|:-|:-|:-|:-|
+${flag-pairs
+|**${flag-1}**|${help-1}|**${flag-2}**|${help-2}|
+}
+|-
+}
+${?has-value-options
+**${options-label}**
+|:-:|:-:|:-:|:-|
+|${short-label}|${long-label}|${value-label}|${description-label}|
+|:-:|:-|:-:|:-|
+${value-option-lines
+${>option-row}
+}
+|-
+${more-options-lines
+*… ${count} ${more-options-label}*
+}
+}
+${?has-badges
+*${badges-label}* ${badge-legend}
+}
+";
+
+/// a template for the "options" section where the flags are flowed
+/// into three columns, for wide terminals
+pub static TEMPLATE_OPTIONS_THREE_COLUMN: &str = "
+${?has-flags
+**${flags-label}**
+|:-|:-|:-|:-|:-|:-|
+${flag-triplets
+|**${flag-1}**|${help-1}|**${flag-2}**|${help-2}|**${flag-3}**|${help-3}|
+}
+|-
+}
+${?has-value-options
+**${options-label}**
+|:-:|:-:|:-:|:-|
+|${short-label}|${long-label}|${value-label}|${description-label}|
+|:-:|:-|:-:|:-|
+${value-option-lines
+${>option-row}
+}
+|-
+${more-options-lines
+*… ${count} ${more-options-label}*
+}
+}
+${?has-badges
+*${badges-label}* ${badge-legend}
+}
+";
+
+/// a template for the "options" section where the options marked
+/// with [Printer::mark_common] are in a first table, and the other
+/// ones in an "Advanced options" table
+///
+/// ```
+/// # use clap::{CommandFactory, Parser};
+/// # #[derive(Parser)]
+/// # struct Args {}
+/// let printer = clap_help::Printer::new(Args::command())
+///     .with("options", clap_help::TEMPLATE_OPTIONS_COMMON);
+/// assert!(printer.check_templates().is_empty());
+/// ```
+pub static TEMPLATE_OPTIONS_COMMON: &str = "
+${?has-common-options
+**${common-options-label}**
+|:-:|:-:|:-:|:-|
+|${short-label}|${long-label}|${value-label}|${description-label}|
+|:-:|:-|:-:|:-|
+${common-option-lines
+${>option-row}
+}
+|-
+
+}
+${?has-advanced-options
+**${advanced-options-label}**
+|:-:|:-:|:-:|:-|
+|${short-label}|${long-label}|${value-label}|${description-label}|
+|:-:|:-|:-:|:-|
+${advanced-option-lines
+${>option-row}
 }
 |-
+}
+${more-options-lines
+*… ${count} ${more-options-label}*
+}
+${?has-badges
+*${badges-label}* ${badge-legend}
+}
 ";
 
 /// Keys used to enable/disable/change templates
 pub static TEMPLATES: &[&str] = &[
+    "banner",
     "title",
     "author",
+    "summary",
+    "before-help",
     "introduction",
+    "toc",
     "usage",
     "positionals",
     "options",
     "subcommands",
+    "external-subcommands",
+    "subcommand-tree",
+    "changelog",
+    "keybindings",
+    "environment",
+    "files",
+    "glossary",
+    "tips",
+    "possible-values",
+    "exit-codes",
+    "links",
+    "after-help",
     "bugs",
 ];
 
+/// The sections listed in the "toc" section, with the keys of the
+/// labels of their headings
+static SECTION_HEADINGS: &[(&str, &str)] = &[
+    ("options", "options-label"),
+    ("subcommands", "subcommands-label"),
+    ("subcommand-tree", "subcommands-label"),
+    ("changelog", "new-since-label"),
+    ("keybindings", "keybindings-label"),
+    ("environment", "environment-label"),
+    ("files", "files-label"),
+    ("glossary", "glossary-label"),
+    ("tips", "tips-label"),
+    ("possible-values", "possible-values-label"),
+    ("exit-codes", "exit-codes-label"),
+    ("links", "see-also-label"),
+];
+
+/// Number of subcommands marked as "most used" when usage stats
+/// are given with [Printer::with_usage_stats]
+const MOST_USED_COUNT: usize = 3;
+
+/// Marker put before the names of the options highlighted
+/// by [Printer::print_help_highlighting]
+static HIGHLIGHT_MARKER: &str = "**▶** ";
+
+/// A change applied to the printer of a subcommand
+type SubcommandOverride<'t> = Arc<dyn Fn(Printer<'t>) -> Printer<'t> + Send + Sync + 't>;
+
+/// A function formatting the default value of an argument
+type DefaultFormatter<'t> = Arc<dyn Fn(&OsStr, &Arg) -> Option<String> + Send + Sync + 't>;
+
+/// A function choosing the colors of the lines of an option
+type OptionStyler<'t> = Arc<dyn Fn(&Arg) -> Option<LineStyle> + Send + Sync + 't>;
+
+// a printer can be built in a thread and used in another one
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<Printer<'static>>();
+};
+
 /// An object which you can configure to print the help of a command
 ///
 /// For example, changing the color of bold text and using an alternate
@@ -111,311 +588,4205 @@ pub static TEMPLATES: &[&str] = &[
 ///
 /// ```
 pub struct Printer<'t> {
-    skin: MadSkin,
-    expander: OwningTemplateExpander<'static>,
+    cmd: Command,
+    skin: Option<MadSkin>,
+    preset: Option<StylePreset>,
+    detection: Detection,
+    legacy_colors: Option<bool>,
+    rendered_skin: OnceLock<MadSkin>,
+    expander: OnceLock<OwningTemplateExpander<'static>>,
+    overrides: ExpanderOverrides,
     template_keys: Vec<&'static str>,
-    templates: HashMap<&'static str, &'t str>,
+    templates: HashMap<&'static str, Cow<'t, str>>,
+    responsive_templates: HashMap<&'static str, Vec<(usize, &'t str)>>,
+    declared_variables: Vec<VariableInfo>,
+    wrap_policy: WrapPolicy,
+    links: Vec<(&'static str, String)>,
+    exit_codes: Vec<(i32, String)>,
+    keybindings: Vec<(String, String, String)>,
+    extra_subcommands: Vec<(String, String)>,
+    usage_counts: Vec<(String, u64)>,
+    deprecated: Vec<(String, String)>,
+    value_ranges: Vec<(String, String)>,
+    badges: Vec<(String, Badge)>,
+    gates: Vec<(String, Gate)>,
+    enabled_features: Vec<String>,
+    gate_display: GateDisplay,
+    platform: Platform,
+    count_levels: Vec<(String, Vec<String>)>,
+    negations: Vec<(String, String)>,
+    changelog_since: Option<String>,
+    changelog: Vec<ChangelogEntry>,
+    subcommand_tree_depth: usize,
+    max_possible_values: Option<usize>,
+    default_formatter: Option<DefaultFormatter<'t>>,
+    option_styler: Option<OptionStyler<'t>>,
+    usage_style: Option<UsageStyle>,
+    current_values: Vec<(String, Vec<String>, &'static str)>,
+    conditions: Vec<(String, bool)>,
+    subcommand_overrides: Vec<(String, SubcommandOverride<'t>)>,
+    files: Vec<(PathBuf, String)>,
+    glossary: Vec<(String, String)>,
+    glossary_highlighting: bool,
+    tips: Vec<String>,
+    hyperlinks: bool,
+    env_values: bool,
+    locale: Locale,
+    direction: Direction,
+    accessibility: AccessibilityMode,
+    table_style: Option<TableStyle>,
+    options_alignment: Vec<Alignment>,
+    banner: Option<String>,
+    decorations: Vec<(&'static str, Decoration)>,
+    compact: bool,
+    max_height: Option<usize>,
+    level: HelpLevel,
+    common: Vec<String>,
+    subcommand_categories: Vec<(String, String)>,
+    partials: Vec<(&'static str, Cow<'t, str>)>,
+    section_vars: Vec<(&'static str, Vec<(&'static str, String)>)>,
+    fixed_width: Option<usize>,
+    min_width: Option<usize>,
+    value_brackets: (String, String),
+    width_strategy: WidthStrategy,
+    heading_levels: Vec<(&'static str, usize)>,
+    pager: bool,
+    user_config_issues: Vec<String>,
+    target: Arc<Mutex<Output>>,
+    #[cfg(feature = "graphics")]
+    logo: Option<ImageSource>,
     pub full_width: bool,
     pub max_width: Option<usize>,
 }
 
+/// What a rendering depends on beside the settings of the printer
+#[derive(Debug, Default, Clone, Copy)]
+struct RenderContext {
+    /// whether the help is the one of the command in
+    /// [Printer::print_help_all], which verifies the `help-all` condition
+    help_all: bool,
+}
+
 impl<'t> Printer<'t> {
-    pub fn new(mut cmd: Command) -> Self {
+    pub fn new(cmd: Command) -> Self {
+        Self::new_with_detection(cmd, Detection::default())
+    }
+
+    /// Create a new printer whose skin is chosen for the background of
+    /// the terminal, queried according to the given policy when the
+    /// help is first rendered to a terminal.
+    ///
+    /// Use [Detection::Disabled] when the terminal must not be queried,
+    /// eg because it would never answer.
+    pub fn new_with_detection(mut cmd: Command, detection: Detection) -> Self {
         cmd.build();
-        let expander = Self::make_expander(&cmd);
         let mut templates = HashMap::new();
-        templates.insert("title", TEMPLATE_TITLE);
-        templates.insert("author", TEMPLATE_AUTHOR);
-        templates.insert("usage", TEMPLATE_USAGE);
+        templates.insert("title", TEMPLATE_TITLE.into());
+        templates.insert("author", TEMPLATE_AUTHOR.into());
+        templates.insert("usage", TEMPLATE_USAGE.into());
 
         if cmd.get_positionals().count() != 0 {
-            templates.insert("positionals", TEMPLATE_POSITIONALS);
+            templates.insert("positionals", TEMPLATE_POSITIONALS.into());
         }
 
-        if cmd.get_opts().count() != 0 {
-            templates.insert("options", TEMPLATE_OPTIONS);
+        if cmd
+            .get_arguments()
+            .any(|a| a.get_short().is_some() || a.get_long().is_some())
+        {
+            templates.insert("options", TEMPLATE_OPTIONS.into());
         }
 
         if cmd.has_subcommands() {
-            templates.insert("subcommands", TEMPLATE_SUBCOMMANDS);
+            templates.insert("subcommands", TEMPLATE_SUBCOMMANDS.into());
+        }
+
+        if cmd.is_allow_external_subcommands_set() {
+            templates.insert("external-subcommands", TEMPLATE_EXTERNAL_SUBCOMMANDS.into());
+        }
+
+        if cmd
+            .get_arguments()
+            .any(|a| a.get_env().is_some() && !a.is_hide_env_set())
+        {
+            templates.insert("environment", TEMPLATE_ENVIRONMENT.into());
+        }
+
+        if cmd.get_before_help().is_some() || cmd.get_before_long_help().is_some() {
+            templates.insert("before-help", TEMPLATE_BEFORE_HELP.into());
+        }
+
+        if cmd.get_after_help().is_some() || cmd.get_after_long_help().is_some() {
+            templates.insert("after-help", TEMPLATE_AFTER_HELP.into());
         }
 
-        Self {
-            skin: Self::make_skin(),
-            expander,
+        let defaults = global_defaults();
+        let printer = Self {
+            cmd,
+            skin: None,
+            preset: defaults.and_then(|config| config.preset).copied(),
+            detection,
+            legacy_colors: None,
+            rendered_skin: OnceLock::new(),
+            expander: OnceLock::new(),
+            overrides: ExpanderOverrides::default(),
             templates,
+            responsive_templates: HashMap::new(),
             template_keys: TEMPLATES.to_vec(),
+            declared_variables: Vec::new(),
+            wrap_policy: WrapPolicy::default(),
+            links: Vec::new(),
+            exit_codes: Vec::new(),
+            keybindings: Vec::new(),
+            extra_subcommands: Vec::new(),
+            usage_counts: Vec::new(),
+            deprecated: Vec::new(),
+            value_ranges: Vec::new(),
+            badges: Vec::new(),
+            gates: Vec::new(),
+            enabled_features: Vec::new(),
+            gate_display: GateDisplay::default(),
+            platform: Platform::current(),
+            count_levels: Vec::new(),
+            negations: Vec::new(),
+            changelog_since: None,
+            changelog: Vec::new(),
+            subcommand_tree_depth: 3,
+            max_possible_values: None,
+            default_formatter: None,
+            option_styler: None,
+            usage_style: None,
+            current_values: Vec::new(),
+            conditions: Vec::new(),
+            subcommand_overrides: Vec::new(),
+            files: Vec::new(),
+            glossary: Vec::new(),
+            glossary_highlighting: false,
+            tips: Vec::new(),
+            hyperlinks: hyperlinks_supported(),
+            env_values: true,
+            locale: Locale::default(),
+            direction: Direction::default(),
+            accessibility: AccessibilityMode::default(),
+            table_style: None,
+            options_alignment: Vec::new(),
+            banner: None,
+            decorations: Vec::new(),
+            compact: false,
+            max_height: None,
+            level: HelpLevel::default(),
+            common: Vec::new(),
+            subcommand_categories: Vec::new(),
+            partials: Vec::new(),
+            section_vars: Vec::new(),
+            fixed_width: None,
+            min_width: None,
+            value_brackets: ("<".to_string(), ">".to_string()),
+            width_strategy: WidthStrategy::default(),
+            heading_levels: Vec::new(),
+            pager: false,
+            user_config_issues: Vec::new(),
+            target: Arc::default(),
+            #[cfg(feature = "graphics")]
+            logo: None,
             full_width: false,
             max_width: None,
+        };
+        match defaults {
+            Some(config) => printer.with_global_config(config),
+            None => printer,
         }
     }
 
+    /// Apply the defaults given with [set_global_defaults], apart from
+    /// the preset, whose skin is chosen in [Self::new]
+    fn with_global_config(mut self, config: &GlobalConfig) -> Self {
+        if config.max_width.is_some() {
+            self.max_width = config.max_width;
+        }
+        if !config.color_choice.is_colored() {
+            self = self.with_skin(MadSkin::no_style());
+        }
+        for (section, template) in &config.templates {
+            let key = section.key();
+            // those sections are only present when the command has
+            // the matching arguments, subcommands or texts
+            let depends_on_command = matches!(
+                section,
+                Section::Positionals
+                    | Section::Options
+                    | Section::Subcommands
+                    | Section::ExternalSubcommands
+                    | Section::Environment
+                    | Section::BeforeHelp
+                    | Section::AfterHelp
+            );
+            if self.templates.contains_key(key) || !depends_on_command {
+                self.templates.insert(key, (*template).into());
+            }
+            if !self.template_keys.contains(&key) {
+                self.template_keys.push(key);
+            }
+        }
+        self
+    }
+
     /// Build a skin for the detected theme of the terminal
     /// (i.e. dark, light, or other)
     pub fn make_skin() -> MadSkin {
-        match terminal_light::luma() {
-            Ok(luma) if luma > 0.85 => MadSkin::default_light(),
-            Ok(luma) if luma < 0.2 => MadSkin::default_dark(),
-            _ => MadSkin::default(),
+        Self::make_skin_with(Detection::default())
+    }
+
+    /// Build a skin for the theme of the terminal, detected according
+    /// to the given policy
+    pub fn make_skin_with(detection: Detection) -> MadSkin {
+        Self::make_skin_for(detection, true)
+    }
+
+    /// Build a skin for the theme of the terminal the help is written
+    /// to, or a skin without style when it's not a terminal
+    fn make_skin_for(detection: Detection, output_is_tty: bool) -> MadSkin {
+        if !output_is_tty {
+            return MadSkin::no_style();
         }
+        auto_skin(terminal_luma_with(detection, output_is_tty))
+    }
+
+    /// Change how the background of the terminal is queried to choose
+    /// between a light and a dark skin: some terminals never answer or
+    /// print the answer in the prompt. The query is done at most once
+    /// per process, with a timeout of 200ms by default.
+    ///
+    /// This only matters when no skin was given, and the terminal is
+    /// queried when the help is first rendered.
+    pub fn with_luma_detection(mut self, detection: Detection) -> Self {
+        self.detection = detection;
+        self.rendered_skin = OnceLock::new();
+        self
     }
 
     /// Use the provided skin
     pub fn with_skin(mut self, skin: MadSkin) -> Self {
-        self.skin = skin;
+        self.skin = Some(skin);
+        self.rendered_skin = OnceLock::new();
         self
     }
 
-    /// Set a maximal width, so that the whole terminal width isn't used.
+    /// The skin given to the printer, or the one chosen for the
+    /// target, queried if needed
+    fn base_skin(&self) -> Cow<'_, MadSkin> {
+        let output_is_tty = self.output().is_tty();
+        match (&self.skin, &self.preset) {
+            (Some(skin), _) => Cow::Borrowed(skin),
+            (None, Some(preset)) => Cow::Owned(preset.skin_for(self.detection, output_is_tty)),
+            (None, None) => Cow::Owned(Self::make_skin_for(self.detection, output_is_tty)),
+        }
+    }
+
+    /// Use the skin of a preset, eg `StylePreset::find("sunset")`.
     ///
-    /// This may make some long sentences easier to read on super wide
-    /// terminals, especially when the whole text is short.
-    /// Depending on your texts and parameters, you may set up a width
-    /// of 100 or 150.
-    pub fn with_max_width(mut self, w: usize) -> Self {
-        self.max_width = Some(w);
+    /// The skin is built when the help is first rendered, for the
+    /// terminal it's written to.
+    pub fn with_preset(mut self, preset: &StylePreset) -> Self {
+        self.skin = None;
+        self.preset = Some(*preset);
+        self.rendered_skin = OnceLock::new();
         self
     }
 
-    /// Give a mutable reference to the current skin
-    /// (by default the automatically selected one)
-    /// so that it can be modified
-    pub fn skin_mut(&mut self) -> &mut MadSkin {
-        &mut self.skin
+    /// Apply the settings the user wrote in `$XDG_CONFIG_HOME/clap-help/config.toml`
+    /// (`~/.config/clap-help/config.toml` by default), so that they can
+    /// personalize the help of all the tools calling this function.
+    ///
+    /// The file may contain:
+    ///
+    /// ```toml
+    /// theme = "sunset"         # the name of a style preset
+    /// max_width = 100
+    /// options_layout = "list"  # table, merged, list, verbose, two-columns or three-columns
+    /// pager = true             # see with_pager
+    /// ```
+    ///
+    /// A missing file is ignored, and so are unknown or invalid entries,
+    /// which are given by [Self::user_config_issues].
+    /// This should be called after the settings of the application,
+    /// so that the ones of the user take precedence.
+    pub fn with_user_config(mut self) -> Self {
+        let Some(config) = read_user_config() else {
+            return self;
+        };
+        self.user_config_issues = config.issues;
+        if let Some(pager) = config.pager {
+            self = self.with_pager(pager);
+        }
+        if let Some(preset) = config.theme.as_deref().and_then(StylePreset::find) {
+            self = self.with_preset(preset);
+        }
+        if let Some(max_width) = config.max_width {
+            self = self.with_max_width(max_width);
+        }
+        let layout = config.options_layout.as_deref().and_then(options_layout);
+        if let Some(template) = layout {
+            if self.templates.contains_key("options") {
+                self.set_template("options", template);
+            }
+        }
+        self
     }
 
-    /// Change a template
-    pub fn set_template(&mut self, key: &'static str, template: &'t str) {
-        self.templates.insert(key, template);
+    /// The entries of the configuration file of the user which were
+    /// ignored by [Self::with_user_config], eg an unknown key or the
+    /// name of an unknown theme, described with their line, so that
+    /// the application can warn about them
+    pub fn user_config_issues(&self) -> &[String] {
+        &self.user_config_issues
     }
 
-    /// Change or add a template
-    pub fn with(mut self, key: &'static str, template: &'t str) -> Self {
-        self.set_template(key, template);
+    /// Show the help printed by [Self::print_help] and
+    /// [Self::try_print_help] in a pager, the one given by `$PAGER`
+    /// or `less -R`, when it's printed to a terminal.
+    ///
+    /// The help is printed as usual when the pager can't be started.
+    pub fn with_pager(mut self, pager: bool) -> Self {
+        self.pager = pager;
         self
     }
 
-    /// Unset a template
-    pub fn without(mut self, key: &'static str) -> Self {
-        self.templates.remove(key);
+    /// Force the replacement of the colors of the skin with the 16
+    /// colors of legacy consoles, or prevent it.
+    ///
+    /// By default, the colors are replaced only on the consoles of old
+    /// Windows versions, which don't render the 256 colors and rgb colors.
+    pub fn force_legacy_colors(mut self, legacy: bool) -> Self {
+        self.legacy_colors = Some(legacy);
+        self.rendered_skin = OnceLock::new();
         self
     }
 
-    /// A mutable reference to the list of template keys, so that you can
-    /// insert new keys, or change their order.
-    /// Any key without matching template will just be ignored
-    pub fn template_keys_mut(&mut self) -> &mut Vec<&'static str> {
-        &mut self.template_keys
+    /// The skin used to render the help, which is the skin of the
+    /// printer, aligned for the direction of the texts, with the
+    /// borders of the table style, and its colors downgraded when
+    /// legacy colors are needed
+    fn displayed_skin(&self) -> &MadSkin {
+        self.rendered_skin.get_or_init(|| {
+            let mut skin = self.base_skin().into_owned();
+            if self.direction == Direction::RightToLeft {
+                skin.paragraph.align = Alignment::Right;
+            }
+            if let Some(table_style) = &self.table_style {
+                table_style.apply_to_skin(&mut skin);
+            }
+            if self.legacy_colors.unwrap_or_else(legacy_colors_needed) {
+                downgrade_skin(&mut skin);
+            }
+            skin
+        })
     }
 
-    /// A mutable reference to the list of template keys, so that you can
-    /// insert new keys, or change their order.
-    /// Any key without matching template will just be ignored
-    #[deprecated(since = "0.6.2", note = "use template_keys_mut instead")]
-    pub fn template_order_mut(&mut self) -> &mut Vec<&'static str> {
-        &mut self.template_keys
+    /// Change the characters around the value names in `${value-braced}`,
+    /// `${value-short-braced}` and `${value-long-braced}`, which are
+    /// `<` and `>` by default, eg to `[` and `]` or `⟨` and `⟩`.
+    ///
+    /// `${value-bare}` is the value name without any bracket.
+    pub fn with_value_brackets(mut self, open: &str, close: &str) -> Self {
+        self.value_brackets = (open.to_string(), close.to_string());
+        self.reset_expander();
+        self
     }
 
-    fn make_expander(cmd: &Command) -> OwningTemplateExpander<'static> {
-        let mut expander = OwningTemplateExpander::new();
-        expander.set_default("");
-
-        let name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
-        expander.set("name", name);
-
-        if let Some(author) = cmd.get_author() {
-            expander.set("author", author);
-        }
+    /// Change how the width of the sections is determined.
+    ///
+    /// By default, all the sections are rendered before the help is
+    /// printed, so that their tables have the same width. With
+    /// [WidthStrategy::Streaming] or [WidthStrategy::Fixed], each
+    /// section is printed as soon as it's rendered, which keeps the
+    /// memory low for huge helps, eg with [Self::print_help_recursive].
+    ///
+    /// The help is still rendered as a whole when a maximal height
+    /// is set with [Self::with_max_height].
+    pub fn with_width_strategy(mut self, strategy: WidthStrategy) -> Self {
+        self.width_strategy = strategy;
+        self
+    }
 
-        if let Some(version) = cmd.get_version() {
-            expander.set("version", version);
-        }
+    /// Set a minimal width, used when the width of the terminal is
+    /// smaller, eg when it's wrongly reported on a CI runner
+    pub fn with_min_width(mut self, w: usize) -> Self {
+        self.min_width = Some(w);
+        self.reset_expander();
+        self
+    }
 
-        let options = cmd
+    /// Set a maximal width, so that the whole terminal width isn't used.
+    ///
+    /// This may make some long sentences easier to read on super wide
+    /// terminals, especially when the whole text is short.
+    /// Depending on your texts and parameters, you may set up a width
+    /// of 100 or 150.
+    pub fn with_max_width(mut self, w: usize) -> Self {
+        self.max_width = Some(w);
+        self.reset_expander();
+        self
+    }
+
+    /// Write the help to another target than the standard output,
+    /// eg [Target::Stderr] for usage errors.
+    ///
+    /// Hyperlinks, colors and the width of the terminal are the ones
+    /// of the target: a [Target::Writer] or a redirected output gets
+    /// no hyperlink, no style unless a skin is given, and a width of
+    /// 80 columns, unless set with [Self::with_fixed_width]. For a
+    /// terminal, the skin is chosen for its background, queried
+    /// according to the policy given to [Self::new_with_detection]
+    /// or [Self::with_luma_detection].
+    ///
+    /// ```
+    /// use clap_help::Target;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_target(Target::Stderr);
+    /// printer.print_usage_error("missing file");
+    /// ```
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.hyperlinks = self.hyperlinks && target.is_tty();
+        self.rendered_skin = OnceLock::new();
+        self.target = Arc::new(Mutex::new(Output::new(target)));
+        self.reset_expander();
+        self
+    }
+
+    /// Use this width instead of the one of the terminal, which
+    /// isn't queried anymore
+    pub fn with_fixed_width(mut self, width: usize) -> Self {
+        self.fixed_width = Some(width);
+        self.reset_expander();
+        self
+    }
+
+    /// Change how long help strings are wrapped in table cells and
+    /// list items.
+    ///
+    /// For example, to have the descriptions of the list template
+    /// aligned with their first line:
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// use clap_help::{HangingIndent, WrapPolicy};
+    ///
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with("options", clap_help::TEMPLATE_OPTIONS_LIST)
+    ///     .with_help_wrap(WrapPolicy {
+    ///         hanging_indent: HangingIndent::AfterSeparator(" : "),
+    ///         ..Default::default()
+    ///     });
+    /// ```
+    pub fn with_help_wrap(mut self, wrap_policy: WrapPolicy) -> Self {
+        self.wrap_policy = wrap_policy;
+        self
+    }
+
+    /// Set a link, eg the "homepage" or the "repository" of the
+    /// application, usable as `${name}` in templates and listed
+    /// in the "links" section.
+    ///
+    /// Links are rendered as clickable OSC 8 hyperlinks when the
+    /// terminal supports them, and as plain urls otherwise.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_link("repository", "https://github.com/Canop/clap-help");
+    /// ```
+    pub fn with_link(mut self, name: &'static str, url: impl Into<String>) -> Self {
+        let url = url.into();
+        let is_known = self
+            .available_variables()
+            .iter()
+            .any(|v| v.name == name && !v.is_sub_loop());
+        if !is_known {
+            self.declare_variable(VariableInfo {
+                example: url.clone().into(),
+                ..VariableInfo::global(name, "")
+            });
+        }
+        if self.links.is_empty() {
+            self.templates.insert("links", TEMPLATE_LINKS.into());
+        }
+        self.links.push((name, url));
+        self.reset_expander();
+        self
+    }
+
+    /// Show or hide the "environment" section, listing the environment
+    /// variables read by the arguments, with their current values
+    /// (unless `hide_env_values` is set on the argument).
+    ///
+    /// It's shown by default when an argument reads a variable.
+    pub fn with_env_section(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.templates
+                .insert("environment", TEMPLATE_ENVIRONMENT.into());
+        } else {
+            self.templates.remove("environment");
+        }
+        self.reset_expander();
+        self
+    }
+
+    /// Show or hide the note telling that any `<name>-<subcommand>`
+    /// program on the PATH is available as a subcommand.
+    ///
+    /// It's shown by default when the command allows external
+    /// subcommands, eg plugins in the way of git or cargo.
+    pub fn with_external_subcommands_note(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.templates
+                .insert("external-subcommands", TEMPLATE_EXTERNAL_SUBCOMMANDS.into());
+        } else {
+            self.templates.remove("external-subcommands");
+        }
+        self.reset_expander();
+        self
+    }
+
+    /// Render the heading of a section, eg `**${options-label}**` in the
+    /// "options" template or the title, as a markdown heading of the
+    /// given level, from 1 to 8, so that sections can be nested, eg
+    /// the title as H1, the subcommands as H2 and their options as H3.
+    ///
+    /// The heading of a template is its first line made only of a bold
+    /// text, or starting with `#`.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_heading_level("subcommands", 2)
+    ///     .with_heading_level("options", 3);
+    /// ```
+    pub fn with_heading_level(mut self, key: impl Into<Section>, level: usize) -> Self {
+        let key = key.into().key();
+        let level = level.clamp(1, 8);
+        match self.heading_levels.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, l)) => *l = level,
+            None => self.heading_levels.push((key, level)),
+        }
+        self
+    }
+
+    /// Return the template with its heading at the level given with
+    /// [Self::with_heading_level], if any
+    fn leveled_template<'s>(&self, key: &str, template: &'s str) -> Cow<'s, str> {
+        match self.heading_levels.iter().find(|(k, _)| *k == key) {
+            Some((_, level)) => set_heading_level(template, *level),
+            None => Cow::Borrowed(template),
+        }
+    }
+
+    /// Show or hide the "toc" section, listing the sections of the
+    /// help, and all the subcommands in [Self::print_help_all].
+    ///
+    /// In the markdown export, the entries link to the sections.
+    pub fn with_toc(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.templates.insert("toc", TEMPLATE_TOC.into());
+        } else {
+            self.templates.remove("toc");
+        }
+        self.reset_expander();
+        self
+    }
+
+    /// List a file used by the application, eg a configuration file
+    /// or a state directory, in the "files" section.
+    ///
+    /// The path may start with `~`, and a path in the home directory
+    /// is displayed with `~`, while `${expanded-path}` is the complete one.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_file("~/.config/my_prog/conf.toml", "Configuration file")
+    ///     .with_file("/var/lib/my_prog", "State directory");
+    /// ```
+    pub fn with_file(mut self, path: impl AsRef<Path>, description: impl Into<String>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let description = description.into();
+        if self.files.is_empty() {
+            self.templates.insert("files", TEMPLATE_FILES.into());
+        }
+        self.files.push((path, description));
+        self.reset_expander();
+        self
+    }
+
+    /// Define a term of the domain of the application, listed with its
+    /// definition in the "glossary" section, so that the help strings
+    /// can use it without explaining it again.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_glossary_term("verb", "A command bound to a key or typed in the input")
+    ///     .with_glossary_term("panel", "One of the trees displayed side by side")
+    ///     .with_glossary_highlighting(true);
+    /// ```
+    pub fn with_glossary_term(
+        mut self,
+        term: impl Into<String>,
+        definition: impl Into<String>,
+    ) -> Self {
+        if self.glossary.is_empty() {
+            self.templates.insert("glossary", TEMPLATE_GLOSSARY.into());
+        }
+        self.glossary.push((term.into(), definition.into()));
+        self.reset_expander();
+        self
+    }
+
+    /// Italicize the occurrences of the terms given with
+    /// [Self::with_glossary_term] in the help strings of the arguments
+    /// and subcommands (not in inline code nor in already styled parts).
+    ///
+    /// It's disabled by default.
+    pub fn with_glossary_highlighting(mut self, enabled: bool) -> Self {
+        self.glossary_highlighting = enabled;
+        self.reset_expander();
+        self
+    }
+
+    /// Show or hide the "tips" section, listing generated tips, eg
+    /// telling that short flags may be combined, and the ones given
+    /// with [Self::with_tip].
+    ///
+    /// It's hidden by default.
+    pub fn with_tips(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.templates.insert("tips", TEMPLATE_TIPS.into());
+        } else {
+            self.templates.remove("tips");
+        }
+        self.reset_expander();
+        self
+    }
+
+    /// Add a tip, as markdown, to the "tips" section, which is then shown
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[arg(short)]
+    /// #     x: bool,
+    /// #     #[arg(short)]
+    /// #     v: bool,
+    /// # }
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_tip("Run `mytool --init` to create the configuration file");
+    /// let help = printer.render_for_test(80, Default::default());
+    /// assert!(help.contains("Short flags may be combined: -xv"));
+    /// assert!(help.contains("to create the configuration file"));
+    /// ```
+    pub fn with_tip(mut self, tip: impl Into<String>) -> Self {
+        if self.tips.is_empty() {
+            self.templates.insert("tips", TEMPLATE_TIPS.into());
+        }
+        self.tips.push(tip.into());
+        self.reset_expander();
+        self
+    }
+
+    /// Document an exit status of the application, listed in the
+    /// "exit-codes" section
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_exit_code(0, "Success")
+    ///     .with_exit_code(1, "The file couldn't be read")
+    ///     .with_exit_code(2, "Invalid arguments");
+    /// ```
+    pub fn with_exit_code(mut self, code: i32, meaning: impl Into<String>) -> Self {
+        let meaning = meaning.into();
+        if self.exit_codes.is_empty() {
+            self.templates
+                .insert("exit-codes", TEMPLATE_EXIT_CODES.into());
+        }
+        self.exit_codes.push((code, meaning));
+        self.reset_expander();
+        self
+    }
+
+    /// Show at most `max` possible values of an option, followed by
+    /// "… and 27 more" when there are more
+    pub fn with_max_possible_values(mut self, max: usize) -> Self {
+        self.max_possible_values = Some(max);
+        self.reset_expander();
+        self
+    }
+
+    /// Format the default values shown in the options with the given
+    /// function instead of displaying them as they are, for example to
+    /// redact secrets or shorten paths.
+    ///
+    /// When the function returns `None`, the default isn't shown.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_default_formatter(|value, arg| {
+    ///         if arg.get_id() == "token" {
+    ///             Some("********".to_string())
+    ///         } else {
+    ///             Some(value.to_string_lossy().to_string())
+    ///         }
+    ///     });
+    /// ```
+    pub fn with_default_formatter<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&OsStr, &Arg) -> Option<String> + Send + Sync + 't,
+    {
+        self.default_formatter = Some(Arc::new(f));
+        self.reset_expander();
+        self
+    }
+
+    /// Set the cargo features, or unstable modes, which are enabled,
+    /// so that the arguments given a [Gate] with [Self::gate] are
+    /// displayed only when their gate is open.
+    ///
+    /// ```
+    /// use clap_help::Gate;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[arg(long)]
+    /// #     jit: bool,
+    /// # }
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_gate("jit", Gate::RequiresFeature("unstable"))?;
+    /// let help = printer.render_for_test(80, Default::default());
+    /// assert!(!help.contains("--jit"));
+    ///
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_enabled_features(["unstable"])
+    ///     .with_gate("jit", Gate::RequiresFeature("unstable"))?;
+    /// let help = printer.render_for_test(80, Default::default());
+    /// assert!(help.contains("--jit"));
+    /// # Ok::<(), clap_help::UnknownArg>(())
+    /// ```
+    pub fn with_enabled_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.enabled_features = features.into_iter().map(Into::into).collect();
+        self.reset_expander();
+        self
+    }
+
+    /// Set whether the arguments whose gate is closed are hidden,
+    /// which is the default, or displayed with a note telling what
+    /// they require
+    pub fn with_gate_display(mut self, display: GateDisplay) -> Self {
+        self.gate_display = display;
+        self.reset_expander();
+        self
+    }
+
+    /// Describe the levels of a counted flag (`ArgAction::Count`), eg
+    /// `-v`, `-vv` and `-vvv` for the "info", "debug" and "trace"
+    /// levels, displayed as `${count-levels}`.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[arg(short, long, action = clap::ArgAction::Count)]
+    /// #     verbose: u8,
+    /// # }
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_count_levels("verbose", &["info", "debug", "trace"]);
+    /// let help = printer.render_for_test(120, Default::default());
+    /// assert!(help.contains("may be repeated"));
+    /// assert!(help.contains("-vv debug"));
+    /// ```
+    pub fn with_count_levels(mut self, name: &str, levels: &[&str]) -> Self {
+        let id = match self.find_option(name) {
+            Some(arg) => arg.get_id().to_string(),
+            None => name.to_string(),
+        };
+        let levels = levels.iter().map(|level| level.to_string()).collect();
+        self.count_levels.retain(|(level_id, _)| *level_id != id);
+        self.count_levels.push((id, levels));
+        self.reset_expander();
+        self
+    }
+
+    /// Set the platform for which the help is rendered, instead of the
+    /// one the application was compiled for, eg to generate the
+    /// documentation of the Windows version, see [Self::only_on]
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.platform = platform;
+        self.reset_expander();
+        self
+    }
+
+    /// Color the lines of some options in the options sections, eg the
+    /// experimental ones in yellow and the dangerous ones in red, with
+    /// the colors returned by the function.
+    ///
+    /// ```
+    /// use clap_help::LineStyle;
+    /// use termimad::crossterm::style::Color;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_option_styler(|arg| match arg.get_id().as_str() {
+    ///         "force" => Some(LineStyle::fg(Color::Red)),
+    ///         "jit" => Some(LineStyle::fg(Color::Yellow)),
+    ///         _ => None,
+    ///     });
+    /// ```
+    pub fn with_option_styler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Arg) -> Option<LineStyle> + Send + Sync + 't,
+    {
+        self.option_styler = Some(Arc::new(f));
+        self.reset_expander();
+        self
+    }
+
+    /// A mutable reference to the styles of the program name, the
+    /// flags, the value names and the brackets of the usage line,
+    /// initialized from the skin.
+    ///
+    /// If the usage template is the default one, it's replaced with
+    /// [TEMPLATE_USAGE_STYLED], which doesn't render the usage as a
+    /// single inline code.
+    ///
+    /// ```
+    /// use termimad::crossterm::style::Color;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let mut printer = clap_help::Printer::new(Args::command());
+    /// printer.usage_style_mut().metavar.set_fg(Color::Cyan);
+    /// ```
+    pub fn usage_style_mut(&mut self) -> &mut UsageStyle {
+        if self.templates.get("usage").map(|t| t.as_ref()) == Some(TEMPLATE_USAGE) {
+            self.templates.insert("usage", TEMPLATE_USAGE_STYLED.into());
+            self.reset_expander();
+        }
+        let usage_style = match self.usage_style.take() {
+            Some(usage_style) => usage_style,
+            None => UsageStyle::from_skin(&self.base_skin()),
+        };
+        self.usage_style.insert(usage_style)
+    }
+
+    /// Show, with the `${current}` variable, the value in effect for
+    /// each option, whether it comes from the command line, the
+    /// environment, or the default.
+    ///
+    /// The values are shown through the formatter given with
+    /// [Self::with_default_formatter], and the ones coming from the
+    /// environment are masked when the argument hides its env values.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let matches = Args::command().get_matches_from(["my_prog"]);
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_matches(&matches);
+    /// ```
+    pub fn with_matches(mut self, matches: &ArgMatches) -> Self {
+        self.current_values.clear();
+        for arg in self.cmd.get_arguments() {
+            let id = arg.get_id().as_str();
+            let Ok(Some(values)) = matches.try_get_raw(id) else {
+                continue;
+            };
+            let source = match matches.value_source(id) {
+                Some(ValueSource::CommandLine) => "command-line-source-label",
+                Some(ValueSource::EnvVariable) => "env-source-label",
+                Some(ValueSource::DefaultValue) => "default-source-label",
+                _ => continue,
+            };
+            let values = values
+                .map(|value| value.to_string_lossy().to_string())
+                .collect();
+            self.current_values.push((id.to_string(), values, source));
+        }
+        self.reset_expander();
+        self
+    }
+
+    /// Add the "possible-values" section, an appendix listing all
+    /// the possible values of the options with their descriptions,
+    /// which is useful when they're truncated in the options table.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_max_possible_values(5)
+    ///     .with_possible_values_appendix();
+    /// ```
+    pub fn with_possible_values_appendix(mut self) -> Self {
+        self.templates
+            .insert("possible-values", TEMPLATE_POSSIBLE_VALUES.into());
+        self.reset_expander();
+        self
+    }
+
+    /// Show the "subcommand-tree" section, where the subcommands and
+    /// their own subcommands, down to the given depth, are drawn as a tree.
+    ///
+    /// It's usually used instead of the "subcommands" section:
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .without("subcommands")
+    ///     .with_subcommand_tree(2);
+    /// ```
+    pub fn with_subcommand_tree(mut self, max_depth: usize) -> Self {
+        self.subcommand_tree_depth = max_depth;
+        if self.cmd.has_subcommands() {
+            self.templates
+                .insert("subcommand-tree", TEMPLATE_SUBCOMMAND_TREE.into());
+        }
+        self.reset_expander();
+        self
+    }
+
+    /// List, in the "changelog" section, the flags and subcommands
+    /// added since the given version (included), eg to let users
+    /// discover the new features of the version they just installed.
+    ///
+    /// An entry without description is described by the help of the
+    /// option or subcommand of the same name.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// use clap_help::ChangelogEntry;
+    ///
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_changelog_since("2.3", [
+    ///         ChangelogEntry::new("2.2", "--sort"), // not listed
+    ///         ChangelogEntry::new("2.3", "--watch"),
+    ///         ChangelogEntry::new("2.4", "export").with_description("Export as JSON"),
+    ///     ]);
+    /// ```
+    pub fn with_changelog_since(
+        mut self,
+        since: impl Into<String>,
+        entries: impl IntoIterator<Item = ChangelogEntry>,
+    ) -> Self {
+        let since = since.into();
+        self.changelog = entries
+            .into_iter()
+            .filter(|entry| compare_versions(&entry.version, &since).is_ge())
+            .collect();
+        self.changelog_since = Some(since);
+        if self.changelog.is_empty() {
+            self.templates.remove("changelog");
+        } else {
+            self.templates
+                .insert("changelog", TEMPLATE_CHANGELOG.into());
+        }
+        self.reset_expander();
+        self
+    }
+
+    /// Document a keyboard shortcut of the application, eg of its TUI,
+    /// listed in the "keybindings" section
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_keybinding("ctrl-q", "quit", "Quit the application")
+    ///     .with_keybinding("/", "search", "Filter the list");
+    /// ```
+    pub fn with_keybinding(
+        mut self,
+        keys: impl Into<String>,
+        action: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        let keybinding = (keys.into(), action.into(), description.into());
+        if self.keybindings.is_empty() {
+            self.templates
+                .insert("keybindings", TEMPLATE_KEYBINDINGS.into());
+        }
+        self.keybindings.push(keybinding);
+        self.reset_expander();
+        self
+    }
+
+    /// List subcommands which aren't known to clap, eg plugins discovered
+    /// at runtime, after the ones of the command, as `(name, about)`.
+    ///
+    /// They're also available in the `${plugin-lines}` sub-loop, for a
+    /// template listing them separately.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_extra_subcommands([("lint", "Lint the sources (plugin)")]);
+    /// ```
+    pub fn with_extra_subcommands<I, N, A>(mut self, subcommands: I) -> Self
+    where
+        I: IntoIterator<Item = (N, A)>,
+        N: Into<String>,
+        A: Into<String>,
+    {
+        self.extra_subcommands.extend(
+            subcommands
+                .into_iter()
+                .map(|(name, about)| (name.into(), about.into())),
+        );
+        if !self.extra_subcommands.is_empty() {
+            self.templates
+                .entry("subcommands")
+                .or_insert_with(|| TEMPLATE_SUBCOMMANDS.into());
+        }
+        self.reset_expander();
+        self
+    }
+
+    /// Sort the subcommands by frequency of use, the most used ones
+    /// being marked as such with `${most-used}`, according to a file
+    /// with one subcommand per line, as `name count`, eg maintained
+    /// with [crate::record_subcommand_usage].
+    ///
+    /// The order isn't changed when the file can't be read.
+    #[cfg(feature = "history")]
+    pub fn with_usage_stats(mut self, path: impl AsRef<std::path::Path>) -> Self {
+        self.usage_counts = crate::history::read_usage_counts(path.as_ref()).unwrap_or_default();
+        self.reset_expander();
+        self
+    }
+
+    /// Force the rendering of links as OSC 8 hyperlinks, or as plain
+    /// urls, instead of relying on the detection of the terminal
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// Use the provided locale for the labels of the templates,
+    /// eg "Options:"
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self.reset_expander();
+        self
+    }
+
+    /// Set the direction of the texts.
+    ///
+    /// With [Direction::RightToLeft], the paragraphs of the skin are
+    /// right aligned.
+    pub fn with_text_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self.rendered_skin = OnceLock::new();
+        self
+    }
+
+    /// Mark an option as deprecated, given by its id, its long name
+    /// (eg `--format`) or its short name (eg `-f`): its name is struck
+    /// out and the note, eg "use `--width` instead", is displayed
+    /// as `${deprecated-note}`.
+    ///
+    /// Options whose help starts with `[deprecated]`,
+    /// `[deprecated: some note]` or `Deprecated: some note`
+    /// are marked as deprecated without this call.
+    ///
+    /// Return an error when no option is found.
+    pub fn mark_deprecated(
+        &mut self,
+        name: &str,
+        note: impl Into<String>,
+    ) -> Result<(), UnknownArg> {
+        let arg = self
+            .find_option(name)
+            .ok_or_else(|| UnknownArg::new(name))?;
+        let id = arg.get_id().to_string();
+        self.deprecated
+            .retain(|(deprecated_id, _)| *deprecated_id != id);
+        self.deprecated.push((id, note.into()));
+        self.reset_expander();
+        Ok(())
+    }
+
+    /// Mark an option as deprecated, like [Self::mark_deprecated]
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     /// Width of the output
+    /// #     #[arg(long)]
+    /// #     cols: Option<u16>,
+    /// # }
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_deprecated("--cols", "use `--width` instead")?;
+    /// let help = printer.render_for_test(80, Default::default());
+    /// assert!(help.contains("--width"));
+    /// # Ok::<(), clap_help::UnknownArg>(())
+    /// ```
+    pub fn with_deprecated(
+        mut self,
+        name: &str,
+        note: impl Into<String>,
+    ) -> Result<Self, UnknownArg> {
+        self.mark_deprecated(name, note)?;
+        Ok(self)
+    }
+
+    /// Display the range of the values accepted by an option, given by
+    /// its id, its long name (eg `--port`) or its short name, as
+    /// `${value-range}` and in `${details-constraints}`.
+    ///
+    /// The range of an integer parsed by clap, eg with
+    /// `value_parser!(u16).range(1..)`, is displayed without this call,
+    /// which is only needed for the options with a custom parser.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     /// Port to listen on
+    /// #     #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    /// #     port: u16,
+    /// #     /// Level of compression
+    /// #     #[arg(long, value_parser = parse_level)]
+    /// #     level: Level,
+    /// # }
+    /// # #[derive(Clone)]
+    /// # struct Level(u8);
+    /// # fn parse_level(s: &str) -> Result<Level, String> {
+    /// #     match s.parse() {
+    /// #         Ok(level @ 1..=9) => Ok(Level(level)),
+    /// #         _ => Err("expected a level from 1 to 9".to_string()),
+    /// #     }
+    /// # }
+    /// let mut printer = clap_help::Printer::new(Args::command());
+    /// printer.set_value_range("--level", 1..=9)?;
+    /// let help = printer.render_for_test(100, Default::default());
+    /// assert!(help.contains("1..=65535"));
+    /// assert!(help.contains("1..=9"));
+    /// # Ok::<(), clap_help::UnknownArg>(())
+    /// ```
+    ///
+    /// An unbounded range removes the range given before. Return an
+    /// error when no option is found.
+    pub fn set_value_range(
+        &mut self,
+        name: &str,
+        range: impl RangeBounds<i64>,
+    ) -> Result<(), UnknownArg> {
+        let arg = self
+            .find_option(name)
+            .ok_or_else(|| UnknownArg::new(name))?;
+        let id = arg.get_id().to_string();
+        self.value_ranges.retain(|(ranged_id, _)| *ranged_id != id);
+        if let Some(range) = format_range(&range) {
+            self.value_ranges.push((id, range));
+        }
+        self.reset_expander();
+        Ok(())
+    }
+
+    /// Display the range of the values accepted by an option, like
+    /// [Self::set_value_range]
+    pub fn with_value_range(
+        mut self,
+        name: &str,
+        range: impl RangeBounds<i64>,
+    ) -> Result<Self, UnknownArg> {
+        self.set_value_range(name, range)?;
+        Ok(self)
+    }
+
+    /// Display an option and its negation, eg `--color` and
+    /// `--no-color`, given by their ids, long names or short names,
+    /// on a single row, as `--[no-]color`, with the help of the
+    /// positive option.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     /// Use colors
+    /// #     #[arg(long, overrides_with = "no_color")]
+    /// #     color: bool,
+    /// #     /// Don't use colors
+    /// #     #[arg(long)]
+    /// #     no_color: bool,
+    /// # }
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_negation_pair("color", "no-color")?;
+    /// let help = printer.render_for_test(80, Default::default());
+    /// assert!(help.contains("--[no-]color"));
+    /// assert!(!help.contains("Don't use colors"));
+    /// # Ok::<(), clap_help::UnknownArg>(())
+    /// ```
+    ///
+    /// Return an error when one of the options isn't found.
+    pub fn pair_negation(&mut self, positive: &str, negative: &str) -> Result<(), UnknownArg> {
+        let pair = (
+            self.find_option(positive)
+                .ok_or_else(|| UnknownArg::new(positive))?
+                .get_id()
+                .to_string(),
+            self.find_option(negative)
+                .ok_or_else(|| UnknownArg::new(negative))?
+                .get_id()
+                .to_string(),
+        );
+        self.negations.retain(|(p, n)| *p != pair.0 && *n != pair.1);
+        self.negations.push(pair);
+        self.reset_expander();
+        Ok(())
+    }
+
+    /// Display an option and its negation on a single row, like
+    /// [Self::pair_negation]
+    pub fn with_negation_pair(
+        mut self,
+        positive: &str,
+        negative: &str,
+    ) -> Result<Self, UnknownArg> {
+        self.pair_negation(positive, negative)?;
+        Ok(self)
+    }
+
+    /// Make an argument, given by its id, its long name (eg `--jit`)
+    /// or its short name, available only when its gate is open, eg
+    /// when a cargo feature is enabled, see [Self::with_enabled_features].
+    ///
+    /// An argument given several gates is available when they're
+    /// all open.
+    ///
+    /// Return an error when no argument is found.
+    pub fn gate(&mut self, name: &str, gate: Gate) -> Result<(), UnknownArg> {
+        let arg = self
+            .find_option(name)
+            .ok_or_else(|| UnknownArg::new(name))?;
+        let gated = (arg.get_id().to_string(), gate);
+        if !self.gates.contains(&gated) {
+            self.gates.push(gated);
+        }
+        self.reset_expander();
+        Ok(())
+    }
+
+    /// Make an argument available only when its gate is open, like
+    /// [Self::gate]
+    pub fn with_gate(mut self, name: &str, gate: Gate) -> Result<Self, UnknownArg> {
+        self.gate(name, gate)?;
+        Ok(self)
+    }
+
+    /// Make an argument, given by its id, its long name or its short
+    /// name, available only on a platform, eg `Platform::Unix`: on the
+    /// other ones, it's hidden or, with [GateDisplay::Mark], displayed
+    /// with a note like "only on Linux".
+    ///
+    /// ```
+    /// use clap_help::Platform;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[arg(long)]
+    /// #     inotify: bool,
+    /// # }
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_platform(Platform::Windows)
+    ///     .with_only_on("inotify", Platform::Linux)?;
+    /// let help = printer.render_for_test(80, Default::default());
+    /// assert!(!help.contains("--inotify"));
+    /// # Ok::<(), clap_help::UnknownArg>(())
+    /// ```
+    ///
+    /// Return an error when no argument is found.
+    pub fn only_on(&mut self, name: &str, platform: Platform) -> Result<(), UnknownArg> {
+        self.gate(name, Gate::OnlyOn(platform))
+    }
+
+    /// Make an argument available only on a platform, like [Self::only_on]
+    pub fn with_only_on(mut self, name: &str, platform: Platform) -> Result<Self, UnknownArg> {
+        self.only_on(name, platform)?;
+        Ok(self)
+    }
+
+    /// Add a badge to an option given by its id, its long name (eg
+    /// `--format`) or its short name (eg `-f`). The badge is displayed
+    /// after the name of the option, and listed in a legend line,
+    /// with its description, after the options.
+    ///
+    /// ```
+    /// use clap_help::Badge;
+    /// use termimad::crossterm::style::Color;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[arg(long)]
+    /// #     turbo: bool,
+    /// # }
+    /// let mut printer = clap_help::Printer::new(Args::command());
+    /// printer.badge(
+    ///     "turbo",
+    ///     Badge::new("experimental")
+    ///         .color(Color::Yellow)
+    ///         .description("may change in future versions"),
+    /// )?;
+    /// let help = printer.render_for_test(80, Default::default());
+    /// assert!(help.contains("--turbo experimental"));
+    /// assert!(help.contains("experimental may change in future versions"));
+    /// # Ok::<(), clap_help::UnknownArg>(())
+    /// ```
+    ///
+    /// Return an error when no option is found.
+    pub fn badge(&mut self, name: &str, badge: Badge) -> Result<(), UnknownArg> {
+        let arg = self
+            .find_option(name)
+            .ok_or_else(|| UnknownArg::new(name))?;
+        self.badges.push((arg.get_id().to_string(), badge));
+        self.reset_expander();
+        Ok(())
+    }
+
+    /// Add a badge to an option, like [Self::badge]
+    pub fn with_badge(mut self, name: &str, badge: Badge) -> Result<Self, UnknownArg> {
+        self.badge(name, badge)?;
+        Ok(self)
+    }
+
+    /// Mark the options most users need, given by their ids, their
+    /// long names (eg `--output`) or their short names (eg `-v`).
+    ///
+    /// The common options are listed first and, at the
+    /// [HelpLevel::Short] level, they're the only ones displayed.
+    /// With [TEMPLATE_OPTIONS_COMMON], the other ones are in an
+    /// "Advanced options" table.
+    ///
+    /// Return false when an option isn't found.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[arg(short, long)]
+    /// #     output: Option<String>,
+    /// #     #[arg(short, long)]
+    /// #     verbose: bool,
+    /// # }
+    /// let mut printer = clap_help::Printer::new(Args::command())
+    ///     .with("options", clap_help::TEMPLATE_OPTIONS_COMMON);
+    /// assert!(printer.mark_common(&["--output", "-v"]));
+    /// ```
+    pub fn mark_common(&mut self, names: &[&str]) -> bool {
+        let mut found = true;
+        for name in names {
+            let Some(arg) = self.find_option(name) else {
+                found = false;
+                continue;
+            };
+            let id = arg.get_id().to_string();
+            if !self.common.contains(&id) {
+                self.common.push(id);
+            }
+        }
+        self.reset_expander();
+        found
+    }
+
+    /// Put a subcommand, or one given with [Self::with_extra_subcommands],
+    /// in a category, eg "Build commands".
+    ///
+    /// With [TEMPLATE_SUBCOMMANDS_GROUPED], the subcommands are listed
+    /// under their categories, in the order of their first use, then
+    /// the other ones under "Other commands".
+    ///
+    /// Return an error when the subcommand isn't found.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser, Subcommand};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[command(subcommand)]
+    /// #     command: Commands,
+    /// # }
+    /// # #[derive(Subcommand)]
+    /// # enum Commands { Build, Add }
+    /// let mut printer = clap_help::Printer::new(Args::command())
+    ///     .with("subcommands", clap_help::TEMPLATE_SUBCOMMANDS_GROUPED);
+    /// printer.categorize_subcommand("build", "Build commands")?;
+    /// printer.categorize_subcommand("add", "Manifest commands")?;
+    /// assert!(printer.categorize_subcommand("remove", "Manifest commands").is_err());
+    /// # Ok::<(), clap_help::UnknownArg>(())
+    /// ```
+    pub fn categorize_subcommand(
+        &mut self,
+        name: &str,
+        category: impl Into<String>,
+    ) -> Result<(), UnknownArg> {
+        let found = self.cmd.find_subcommand(name).is_some()
+            || self.extra_subcommands.iter().any(|(n, _)| n == name);
+        if !found {
+            return Err(UnknownArg::new(name));
+        }
+        self.subcommand_categories.retain(|(n, _)| n != name);
+        self.subcommand_categories
+            .push((name.to_string(), category.into()));
+        self.reset_expander();
+        Ok(())
+    }
+
+    /// Put a subcommand in a category, like [Self::categorize_subcommand]
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser, Subcommand};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[command(subcommand)]
+    /// #     command: Commands,
+    /// # }
+    /// # #[derive(Subcommand)]
+    /// # enum Commands { Build, Add }
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with("subcommands", clap_help::TEMPLATE_SUBCOMMANDS_GROUPED)
+    ///     .with_subcommand_category("build", "Build commands")?
+    ///     .with_subcommand_category("add", "Manifest commands")?;
+    /// # Ok::<(), clap_help::UnknownArg>(())
+    /// ```
+    pub fn with_subcommand_category(
+        mut self,
+        name: &str,
+        category: impl Into<String>,
+    ) -> Result<Self, UnknownArg> {
+        self.categorize_subcommand(name, category)?;
+        Ok(self)
+    }
+
+    /// Show a banner, eg a logo in ASCII art, at the top of the help.
+    ///
+    /// When the banner is wider than the terminal, only the name
+    /// of the program is shown.
+    pub fn with_banner(mut self, banner: impl Into<String>) -> Self {
+        self.banner = Some(banner.into());
+        self.templates.insert("banner", TEMPLATE_BANNER.into());
+        self.reset_expander();
+        self
+    }
+
+    /// Show, at the top of the help, the name of the program as
+    /// large ASCII art, see [crate::figlet_text]
+    #[cfg(feature = "figlet")]
+    pub fn with_figlet_banner(self) -> Self {
+        let cmd = &self.cmd;
+        let name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+        let banner = figlet_text(name);
+        self.with_banner(banner)
+    }
+
+    /// Show a small logo above the help when the terminal can display
+    /// images with the protocol of kitty or the one of iTerm2, and
+    /// nothing otherwise
+    #[cfg(feature = "graphics")]
+    pub fn with_logo(mut self, source: ImageSource) -> Self {
+        self.logo = Some(source);
+        self
+    }
+
+    /// Make the help fit in less lines: no blank line between the
+    /// sections, the options as a list, unless their template was
+    /// changed, and only the first paragraph of the introduction.
+    ///
+    /// This is usually combined with [Self::with_max_height].
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Remove the last options when the help would be higher than
+    /// `rows` lines, with a note telling how many options are missing
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let locale = clap_help::Locale::default()
+    ///     .with("more-options-label", "more options, see `--help-full`");
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_compact(true)
+    ///     .with_max_height(24)
+    ///     .with_locale(locale);
+    /// ```
+    pub fn with_max_height(mut self, rows: usize) -> Self {
+        self.max_height = Some(rows);
+        self
+    }
+
+    /// Set how much of the help is displayed, eg [HelpLevel::Short]
+    /// when the user typed `-h` and [HelpLevel::Full] for `--help`.
+    ///
+    /// The short help doesn't have the sections detailing the command,
+    /// like "environment" or "exit-codes", nor the arguments with
+    /// `hide_short_help`.
+    ///
+    /// ```
+    /// use clap_help::HelpLevel;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_level(HelpLevel::Short);
+    /// ```
+    pub fn with_level(mut self, level: HelpLevel) -> Self {
+        self.level = level;
+        self.reset_expander();
+        self
+    }
+
+    /// Draw a box around a section, or center it in the terminal
+    ///
+    /// ```
+    /// use clap_help::Decoration;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .decorate_section("title", Decoration::Centered)
+    ///     .decorate_section("usage", Decoration::Boxed);
+    /// ```
+    pub fn decorate_section(mut self, key: impl Into<Section>, decoration: Decoration) -> Self {
+        let key = key.into().key();
+        self.decorations.retain(|(k, _)| *k != key);
+        self.decorations.push((key, decoration));
+        self
+    }
+
+    /// Apply the decoration of the section, if any, to its rendered text
+    fn decorated(&self, key: &str, text: String) -> String {
+        match self.decorations.iter().find(|(k, _)| *k == key) {
+            Some(&(_, decoration)) => decorate(
+                &text,
+                decoration,
+                self.displayed_skin(),
+                self.available_width(),
+            ),
+            None => text,
+        }
+    }
+
+    /// Set the alignments of the columns of the options tables, the
+    /// first alignment applying to the first column (usually "short"),
+    /// instead of the ones of the templates
+    ///
+    /// ```
+    /// use termimad::Alignment;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_options_alignment(&[Alignment::Right, Alignment::Left]);
+    /// ```
+    pub fn with_options_alignment(mut self, alignments: &[Alignment]) -> Self {
+        self.options_alignment = alignments.to_vec();
+        self
+    }
+
+    /// Change how the tables are drawn, eg with ASCII chars only,
+    /// or without borders, whatever the skin.
+    ///
+    /// ```
+    /// use clap_help::{TableBorders, TableStyle};
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_table_style(TableStyle {
+    ///         borders: TableBorders::Ascii,
+    ///         row_separators: true,
+    ///         ..Default::default()
+    ///     });
+    /// ```
+    pub fn with_table_style(mut self, table_style: TableStyle) -> Self {
+        self.table_style = Some(table_style);
+        self.rendered_skin = OnceLock::new();
+        self
+    }
+
+    /// Set the accessibility mode, eg [AccessibilityMode::TextMarkers]
+    /// so that no information is given only by colors, which is better
+    /// combined with a colorblind-safe preset like `deuteranopia`
+    pub fn with_accessibility(mut self, accessibility: AccessibilityMode) -> Self {
+        self.accessibility = accessibility;
+        self.reset_expander();
+        self
+    }
+
+    /// Give a mutable reference to the current skin
+    /// (by default the automatically selected one)
+    /// so that it can be modified
+    pub fn skin_mut(&mut self) -> &mut MadSkin {
+        let skin = match self.skin.take() {
+            Some(skin) => skin,
+            None => self.base_skin().into_owned(),
+        };
+        self.rendered_skin = OnceLock::new();
+        self.skin.insert(skin)
+    }
+
+    /// Change a template
+    ///
+    /// The key may be a [Section] or a string, eg `"options"`.
+    pub fn set_template(&mut self, key: impl Into<Section>, template: &'t str) {
+        let key = key.into().key();
+        self.responsive_templates.remove(key);
+        self.templates.insert(key, template.into());
+        self.reset_expander();
+    }
+
+    /// Change or add a template
+    pub fn with(mut self, key: impl Into<Section>, template: &'t str) -> Self {
+        self.set_template(key, template);
+        self
+    }
+
+    /// Add a section, with variables which are set in its template
+    /// only, so that they don't conflict with the ones of other sections.
+    ///
+    /// The values of the variables are markdown. Besides them, the
+    /// template may use the variables describing the command, eg
+    /// `${name}` or `${version}`, but not the sub-loops listing its
+    /// arguments nor the variables set with [Self::expander_mut].
+    ///
+    /// The section is added at the end of the section order unless
+    /// its key is already there.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// use std::collections::HashMap;
+    ///
+    /// let mut printer = clap_help::Printer::new(Args::command());
+    /// printer.add_section(
+    ///     "environment",
+    ///     "**Environment:**\n* `${var}`: ${description}\n",
+    ///     HashMap::from([
+    ///         ("var", "MY_PROG_LOG".to_string()),
+    ///         ("description", "log level".to_string()),
+    ///     ]),
+    /// );
+    /// ```
+    pub fn add_section(
+        &mut self,
+        key: impl Into<Section>,
+        template: &str,
+        vars: HashMap<&'static str, String>,
+    ) {
+        let key = key.into().key();
+        let mut vars: Vec<(&'static str, String)> = vars.into_iter().collect();
+        vars.sort_unstable();
+        self.section_vars.retain(|(k, _)| *k != key);
+        self.section_vars.push((key, vars));
+        self.responsive_templates.remove(key);
+        self.templates.insert(key, template.to_string().into());
+        if !self.template_keys.contains(&key) {
+            self.template_keys.push(key);
+        }
+        self.reset_expander();
+    }
+
+    /// Unset a template
+    ///
+    /// ```
+    /// use clap_help::Section;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .without(Section::Author)
+    ///     .without("bugs");
+    /// ```
+    pub fn without(mut self, key: impl Into<Section>) -> Self {
+        let key = key.into().key();
+        self.responsive_templates.remove(key);
+        self.templates.remove(key);
+        self.reset_expander();
+        self
+    }
+
+    /// Use different templates for a section depending on the available
+    /// width, each template being given with the minimal width from which
+    /// it's used.
+    ///
+    /// For example, to have the options rendered as a list on narrow
+    /// terminals and as a table on wider ones:
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_responsive("options", &[
+    ///         (0, clap_help::TEMPLATE_OPTIONS_LIST),
+    ///         (100, clap_help::TEMPLATE_OPTIONS),
+    ///     ]);
+    /// ```
+    pub fn with_responsive(
+        mut self,
+        key: impl Into<Section>,
+        templates: &[(usize, &'t str)],
+    ) -> Self {
+        let key = key.into().key();
+        let mut templates = templates.to_vec();
+        templates.sort_by_key(|&(min_width, _)| min_width);
+        self.responsive_templates.insert(key, templates);
+        self.reset_expander();
+        self
+    }
+
+    /// Set a condition which can be tested in templates, in addition
+    /// to the built-in ones (eg `has-subcommands`), or override one.
+    ///
+    /// The lines between `${?name` and `}` are only rendered when the
+    /// condition is verified, and the ones between `${?!name` and `}`
+    /// when it's not:
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_condition("advanced", false)
+    ///     .with(
+    ///         "introduction",
+    ///         "${?!advanced\n*run with `--help-all` for advanced flags*\n}",
+    ///     );
+    /// // without with_condition, "advanced" would be an unknown condition
+    /// assert!(printer.check_templates().is_empty());
+    /// ```
+    pub fn with_condition(mut self, name: impl Into<String>, verified: bool) -> Self {
+        let name = name.into();
+        self.conditions.retain(|(n, _)| *n != name);
+        self.conditions.push((name, verified));
+        self
+    }
+
+    /// Define a reusable template fragment, which templates include
+    /// with `${>name}`.
+    ///
+    /// The predefined partials, listed in [PARTIALS], may be redefined,
+    /// eg to change the row of the options in all the options templates.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .define_partial("option-description", "${help}${default}");
+    /// ```
+    pub fn define_partial(mut self, name: &'static str, fragment: &'t str) -> Self {
+        self.partials.retain(|(n, _)| *n != name);
+        self.partials.push((name, fragment.into()));
+        self
+    }
+
+    /// Replace the references to partials, remove the conditional
+    /// blocks of the template whose condition isn't verified, and the columns of the options tables which
+    /// would be empty (eg the "short" one when no option has a short name)
+    fn prepare_template<'s>(&self, template: &'s str, ctx: RenderContext) -> Cow<'s, str> {
+        let template = expand_partials(template, |name| self.partial(name));
+        let template = match apply_style_hints(&template) {
+            Cow::Owned(styled) => Cow::Owned(styled),
+            Cow::Borrowed(_) => template,
+        };
+        let template =
+            match apply_conditions(&template, |name| self.is_condition_verified(name, ctx)) {
+                Cow::Owned(kept) => Cow::Owned(kept),
+                Cow::Borrowed(_) => template,
+            };
+        let template = match align_columns(&template, OPTION_SUB_LOOPS, &self.options_alignment) {
+            Cow::Owned(aligned) => Cow::Owned(aligned),
+            Cow::Borrowed(_) => template,
+        };
+        let options = self.shown_options();
+        let mut empty_columns = Vec::new();
+        if !options.iter().any(|a| a.get_short().is_some()) {
+            empty_columns.push("${short}");
+        }
+        if !options.iter().any(|a| a.get_long().is_some()) {
+            empty_columns.push("${long}");
+        }
+        if !options.iter().any(|a| a.get_action().takes_values()) {
+            empty_columns.extend(["${value}", "${value-styled}", "${value-braced}"]);
+        }
+        match remove_columns(&template, &sub_loops, &empty_columns) {
+            Cow::Owned(reduced) => Cow::Owned(reduced),
+            Cow::Borrowed(_) => template,
+        }
+    }
+
+    /// The options displayed at the level of the help, the common
+    /// ones first
+    fn shown_options(&self) -> Vec<&Arg> {
+        let mut options: Vec<&Arg> = self
+            .cmd
+            .get_arguments()
+            .filter(|a| self.shows_arg(a))
+            .filter(|a| a.get_short().is_some() || a.get_long().is_some())
+            .filter(|a| !self.negations.iter().any(|(_, n)| a.get_id() == n.as_str()))
+            .collect();
+        if !self.common.is_empty() {
+            if self.level == HelpLevel::Short {
+                options.retain(|a| self.is_common(a));
+            }
+            options.sort_by_key(|a| !self.is_common(a));
+        }
+        options
+    }
+
+    /// Tell whether the argument is displayed, according to the help
+    /// level and to its gate
+    fn shows_arg(&self, arg: &Arg) -> bool {
+        self.level.shows_arg(arg)
+            && (self.gate_display == GateDisplay::Mark || self.closed_gate(arg).is_none())
+    }
+
+    /// Return the arguments listed in the "environment" section: the
+    /// displayed ones which can be set by an environment variable
+    fn env_args(&self) -> Vec<&Arg> {
+        self.cmd
             .get_arguments()
-            .filter(|a| !a.is_hide_set())
-            .filter(|a| a.get_short().is_some() || a.get_long().is_some());
+            .filter(|a| a.get_env().is_some() && !a.is_hide_env_set() && self.shows_arg(a))
+            .collect()
+    }
+
+    /// Return the gate of the argument, if it's closed
+    fn closed_gate(&self, arg: &Arg) -> Option<Gate> {
+        self.gates
+            .iter()
+            .filter(|(id, _)| arg.get_id() == id.as_str())
+            .map(|&(_, gate)| gate)
+            .find(|gate| !gate.is_open(&self.enabled_features, self.platform))
+    }
+
+    /// Return the note telling what requires an argument whose gate
+    /// is closed
+    fn gate_note(&self, arg: &Arg) -> Option<String> {
+        match self.closed_gate(arg)? {
+            Gate::RequiresFeature(feature) => Some(format!(
+                " *({} `{feature}`)*",
+                self.label("requires-feature-label"),
+            )),
+            Gate::OnlyOn(platform) => {
+                Some(format!(" *({} {platform})*", self.label("only-on-label"),))
+            }
+        }
+    }
+
+    /// Return the short and long names of an option, as displayed,
+    /// eg `-c` and `--[no-]color` for an option paired with its
+    /// negation by [Self::pair_negation]
+    fn option_names(&self, arg: &Arg) -> (Option<String>, Option<String>) {
+        let short = arg.get_short().map(|short| format!("-{short}"));
+        let long = arg.get_long().map(|long| format!("--{long}"));
+        let negation = self
+            .negations
+            .iter()
+            .find(|(p, _)| arg.get_id() == p.as_str())
+            .and_then(|(_, n)| self.cmd.get_arguments().find(|a| a.get_id() == n.as_str()));
+        let Some(negation) = negation else {
+            return (short, long);
+        };
+        let join = |name: Option<String>, negative: Option<String>| match (name, negative) {
+            (Some(name), Some(negative)) => Some(format!("{name}, {negative}")),
+            (name, negative) => name.or(negative),
+        };
+        let short = join(short, negation.get_short().map(|short| format!("-{short}")));
+        let long = match (arg.get_long(), negation.get_long()) {
+            (Some(long), Some(negative)) if negative == format!("no-{long}") => {
+                Some(format!("--[no-]{long}"))
+            }
+            (_, negative) => join(long, negative.map(|long| format!("--{long}"))),
+        };
+        (short, long)
+    }
+
+    /// Return the names of an option, eg `-c, --[no-]color`
+    fn flag_name(&self, arg: &Arg) -> Option<String> {
+        match self.option_names(arg) {
+            (Some(short), Some(long)) => Some(format!("{short}, {long}")),
+            (short, long) => short.or(long),
+        }
+    }
+
+    /// Return the generated tips, then the ones given with [Self::with_tip]
+    fn all_tips(&self) -> Vec<String> {
+        let mut tips = Vec::new();
+        // the flags which can be combined, eg `-xvf`
+        let shorts: String = self
+            .shown_options()
+            .iter()
+            .filter(|a| !a.get_action().takes_values())
+            .filter(|a| !matches!(a.get_action(), ArgAction::Help | ArgAction::Version))
+            .filter_map(|a| a.get_short())
+            .take(3)
+            .collect();
+        if shorts.chars().count() > 1 {
+            tips.push(format!("{} `-{shorts}`", self.label("combine-flags-tip")));
+        }
+        tips.extend(self.tips.iter().cloned());
+        tips
+    }
+
+    /// Tell whether the option was marked as common
+    fn is_common(&self, arg: &Arg) -> bool {
+        self.common.iter().any(|id| arg.get_id() == id.as_str())
+    }
+
+    /// Return the fragment of a partial, either defined with
+    /// [Self::define_partial] or predefined
+    fn partial(&self, name: &str) -> Option<&str> {
+        self.partials
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, fragment)| fragment.as_ref())
+            .or_else(|| {
+                PARTIALS
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, fragment)| *fragment)
+            })
+    }
+
+    /// The names of the conditions templates may test: the built-in
+    /// ones and the ones set with [Self::with_condition]
+    fn known_conditions(&self) -> Vec<&str> {
+        let mut conditions = BUILTIN_CONDITIONS.to_vec();
+        conditions.extend(self.conditions.iter().map(|(name, _)| name.as_str()));
+        conditions
+    }
+
+    /// Tell whether a condition of the templates is verified
+    fn is_condition_verified(&self, name: &str, ctx: RenderContext) -> bool {
+        if let Some((_, verified)) = self.conditions.iter().find(|(n, _)| n == name) {
+            return *verified;
+        }
+        let cmd = &self.cmd;
+        let options = || self.shown_options().into_iter();
+        match name {
+            "has-options" => options().next().is_some(),
+            "has-flags" => options().any(|a| !a.get_action().takes_values()),
+            "has-value-options" => options().any(|a| a.get_action().takes_values()),
+            "has-common-options" => options().any(|a| self.is_common(a)),
+            "has-advanced-options" => options().any(|a| !self.is_common(a)),
+            "has-hidden-options" => cmd.get_arguments().any(Arg::is_hide_set),
+            "has-possible-values" => options().any(|a| !a.get_possible_values().is_empty()),
+            "has-badges" => options().any(|a| !self.badges_md(a).is_empty()),
+            "has-positionals" => cmd.get_positionals().any(|a| self.shows_arg(a)),
+            "has-subcommands" => {
+                cmd.get_subcommands().any(|s| !s.is_hide_set())
+                    || !self.extra_subcommands.is_empty()
+            }
+            "has-plugins" => !self.extra_subcommands.is_empty(),
+            "has-tips" => !self.all_tips().is_empty(),
+            "has-environment" => !self.env_args().is_empty(),
+            "help-all" => ctx.help_all,
+            "has-author" => cmd.get_author().is_some(),
+            "has-version" => cmd.get_version().is_some(),
+            _ => false,
+        }
+    }
+
+    /// Return the template to use for a key, for the given available width
+    fn template_for(&self, key: &str, width: usize) -> Option<&str> {
+        if !self.level.shows_section(key) {
+            return None;
+        }
+        self.responsive_templates
+            .get(key)
+            .and_then(|templates| {
+                templates
+                    .iter()
+                    .rev()
+                    .find(|&&(min_width, _)| min_width <= width)
+                    .map(|&(_, template)| template)
+            })
+            .or_else(|| self.templates.get(key).map(|template| template.as_ref()))
+            .map(|template| {
+                if self.compact && key == "options" && template == TEMPLATE_OPTIONS {
+                    TEMPLATE_OPTIONS_LIST
+                } else {
+                    template
+                }
+            })
+    }
+
+    /// The width of the terminal, unless a fixed width is set
+    fn terminal_width(&self) -> usize {
+        let width = match self.fixed_width {
+            Some(width) => width,
+            None => terminal_width(self.output().is_tty()),
+        };
+        width.max(self.min_width.unwrap_or(0))
+    }
+
+    /// The width available for the help: the terminal's one,
+    /// unless a smaller `max_width` is set
+    fn available_width(&self) -> usize {
+        let width = self.terminal_width();
+        match self.max_width {
+            Some(max_width) => width.min(max_width),
+            None => width,
+        }
+    }
+
+    /// Set the order of the sections, given by their template keys,
+    /// eg `"options"` or `Section::Options.key()`.
+    ///
+    /// Sections which aren't listed aren't printed, and keys without
+    /// matching template are ignored.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// use clap_help::Section;
+    ///
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_section_order(&["title", "usage", Section::Introduction.key(), "options"])?;
+    /// let empty = clap_help::Printer::new(Args::command())
+    ///     .with_section_order(&[])?;
+    /// assert!(clap_help::Printer::new(Args::command())
+    ///     .with_section_order(&["usage", "options", "usage"])
+    ///     .is_err());
+    /// # Ok::<(), clap_help::SectionOrderError>(())
+    /// ```
+    ///
+    /// An error is returned when a key is given twice.
+    pub fn with_section_order(mut self, keys: &[&'static str]) -> Result<Self, SectionOrderError> {
+        for (i, key) in keys.iter().enumerate() {
+            if keys[..i].contains(key) {
+                return Err(SectionOrderError::DuplicateSection(key));
+            }
+        }
+        self.template_keys = keys.to_vec();
+        self.reset_expander();
+        Ok(self)
+    }
+
+    /// Insert a section just before another one, or move it there
+    /// if it's already in the section order
+    pub fn insert_section_before(
+        &mut self,
+        key: impl Into<Section>,
+        relative_to: impl Into<Section>,
+    ) -> Result<(), SectionOrderError> {
+        self.insert_section(key.into().key(), relative_to.into().key(), 0)
+    }
+
+    /// Insert a section just after another one, or move it there
+    /// if it's already in the section order
+    pub fn insert_section_after(
+        &mut self,
+        key: impl Into<Section>,
+        relative_to: impl Into<Section>,
+    ) -> Result<(), SectionOrderError> {
+        self.insert_section(key.into().key(), relative_to.into().key(), 1)
+    }
+
+    fn insert_section(
+        &mut self,
+        key: &'static str,
+        relative_to: &'static str,
+        offset: usize,
+    ) -> Result<(), SectionOrderError> {
+        if !self.template_keys.contains(&relative_to) {
+            return Err(SectionOrderError::UnknownSection(relative_to));
+        }
+        if key == relative_to {
+            return Ok(());
+        }
+        self.template_keys.retain(|&k| k != key);
+        let idx = self
+            .template_keys
+            .iter()
+            .position(|&k| k == relative_to)
+            .unwrap_or_default();
+        self.template_keys.insert(idx + offset, key);
+        self.reset_expander();
+        Ok(())
+    }
+
+    /// The sections, in the order they're rendered
+    ///
+    /// ```
+    /// use clap_help::Section;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command());
+    /// assert_eq!(printer.template_keys(), Section::ALL);
+    /// ```
+    pub fn template_keys(&self) -> Vec<Section> {
+        self.template_keys.iter().map(|&key| key.into()).collect()
+    }
+
+    /// A mutable reference to the list of template keys, so that you can
+    /// insert new keys, or change their order.
+    /// Any key without matching template will just be ignored
+    pub fn template_keys_mut(&mut self) -> &mut Vec<&'static str> {
+        self.reset_expander();
+        &mut self.template_keys
+    }
+
+    /// A mutable reference to the list of template keys, so that you can
+    /// insert new keys, or change their order.
+    /// Any key without matching template will just be ignored
+    #[deprecated(since = "0.6.2", note = "use template_keys_mut instead")]
+    pub fn template_order_mut(&mut self) -> &mut Vec<&'static str> {
+        self.reset_expander();
+        &mut self.template_keys
+    }
+
+    /// Build the expander, with at most `option_limit` options, and the
+    /// options whose ids are given highlighted
+    fn make_expander(
+        &self,
+        option_limit: Option<usize>,
+        highlighted: &[&str],
+    ) -> OwningTemplateExpander<'static> {
+        let mut expander = self.base_expander();
+        let cmd = &self.cmd;
+
+        if let Some(banner) = &self.banner {
+            let fits = banner.lines().all(|line| line.width() <= self.help_width());
+            if fits {
+                for line in banner.lines() {
+                    expander.sub("banner-lines").set("line", line);
+                }
+            } else {
+                let name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+                expander.sub("banner-lines").set("line", name);
+            }
+        }
+
+        let options = self.shown_options();
+        let shown = option_limit.unwrap_or(usize::MAX).min(options.len());
+        if shown < options.len() {
+            expander
+                .sub("more-options-lines")
+                .set("count", options.len() - shown);
+        }
+        if let Some(legend) = self.badge_legend(&options) {
+            expander.set_md("badge-legend", legend);
+        }
+        let mut flags = Vec::new();
+        for &arg in &options[..shown] {
+            let hl = highlighted.contains(&arg.get_id().as_str());
+            // the values are computed once for the 3 sub-loops
+            let line = self.option_line(arg, hl);
+            line.fill(expander.sub("option-lines"));
+            if self.is_common(arg) {
+                line.fill(expander.sub("common-option-lines"));
+            } else {
+                line.fill(expander.sub("advanced-option-lines"));
+            }
+            self.fill_possible_value_lines(&mut expander, arg);
+            if arg.get_action().takes_values() {
+                line.fill_last(expander.sub("value-option-lines"));
+            } else {
+                flags.push(arg);
+            }
+        }
+        self.fill_flag_rows(&mut expander, "flag-pairs", &flags, 2);
+        self.fill_flag_rows(&mut expander, "flag-triplets", &flags, 3);
+
+        let positionals = cmd.get_positionals().enumerate();
+        for (index, arg) in positionals.filter(|(_, a)| self.shows_arg(a)) {
+            let Some(key) = arg.get_value_names().and_then(|arr| arr.first()) else {
+                continue;
+            };
+
+            let mut line = LineValues::new();
+            line.set("key", key);
+            line.set("index", index + 1);
+            line.set("id", arg.get_id());
+            line.set("anchor", arg_anchor(arg));
+            let raw_label = if arg.is_last_set() {
+                Some("raw-last-label")
+            } else if arg.is_trailing_var_arg_set() {
+                Some("raw-trailing-label")
+            } else if arg.is_allow_hyphen_values_set() {
+                Some("raw-hyphen-label")
+            } else {
+                None
+            };
+            if let Some(raw_label) = raw_label {
+                line.set_md("details-raw", format!(" *({})*", self.label(raw_label)));
+            }
+            self.fill_arg_details(&mut line, arg);
+
+            if let Some(help) = arg.get_help() {
+                line.set("help", help);
+            }
+            line.fill_last(expander.sub("positional-lines"));
+        }
+
+        let mut subcommands: Vec<(&str, String)> = cmd
+            .get_subcommands()
+            .filter(|s| !s.is_hide_set())
+            .map(|s| {
+                let about = s.get_about().map(|a| a.to_string()).unwrap_or_default();
+                (s.get_name(), about)
+            })
+            .collect();
+        for (name, about) in &self.extra_subcommands {
+            subcommands.push((name, about.clone()));
+            let sub = expander.sub("plugin-lines");
+            sub.set("name", name);
+            sub.set_md("help", self.help_md(about));
+        }
+        let usage_count = |name: &str| {
+            self.usage_counts
+                .iter()
+                .find(|(n, _)| n == name)
+                .map_or(0, |(_, count)| *count)
+        };
+        if !self.usage_counts.is_empty() {
+            subcommands.sort_by_key(|(name, _)| std::cmp::Reverse(usage_count(name)));
+        }
+        for (idx, (name, about)) in subcommands.iter().enumerate() {
+            let sub = expander.sub("subcommand-lines");
+            sub.set("name", *name);
+            sub.set_md("help", self.help_md(about));
+            if let Some(subcommand) = cmd.find_subcommand(name) {
+                sub.set("sub-usage", self.sub_usage(subcommand));
+            }
+            if idx < MOST_USED_COUNT && usage_count(name) > 0 {
+                sub.set_md(
+                    "most-used",
+                    format!(" *({})*", self.label("most-used-label")),
+                );
+            }
+        }
+
+        self.fill_subcommand_group_lines(&mut expander);
+
+        Self::fill_subcommand_tree(&mut expander, cmd, "", self.subcommand_tree_depth);
+
+        for arg in self.env_args() {
+            let Some(var) = arg.get_env() else {
+                continue;
+            };
+            let sub = expander.sub("env-lines");
+            sub.set("var", var.to_string_lossy());
+            if let Some(flag) = self.flag_name(arg) {
+                sub.set("flag", flag);
+            }
+            if let Some(help) = arg.get_help() {
+                sub.set_md("help", self.help_md(&help.to_string()));
+            }
+            if let Some(value) = std::env::var_os(var).filter(|_| self.env_values) {
+                if arg.is_hide_env_values_set() {
+                    sub.set("value", "***");
+                } else {
+                    sub.set("value", value.to_string_lossy());
+                }
+            }
+        }
+
+        if let Some(since) = &self.changelog_since {
+            expander.set("changelog-since", since);
+        }
+        for entry in &self.changelog {
+            let sub = expander.sub("changelog-lines");
+            sub.set("version", &entry.version);
+            sub.set("name", &entry.name);
+            let description = entry.description.clone().or_else(|| {
+                let help = match self.find_option(&entry.name) {
+                    Some(arg) => arg.get_help(),
+                    None => self
+                        .cmd
+                        .find_subcommand(&entry.name)
+                        .and_then(|subcommand| subcommand.get_about()),
+                };
+                help.map(|help| help.to_string())
+            });
+            if let Some(description) = description {
+                sub.set_md("description", description);
+            }
+        }
+
+        for keybinding in &self.keybindings {
+            Self::fill_keybinding_line(&mut expander, keybinding);
+        }
+
+        for (path, description) in &self.files {
+            Self::fill_file_line(&mut expander, path, description);
+        }
+
+        for (term, definition) in &self.glossary {
+            let sub = expander.sub("glossary-lines");
+            sub.set("term", term);
+            sub.set_md("definition", definition);
+        }
+
+        for tip in self.all_tips() {
+            expander.sub("tips-lines").set_md("tip", tip);
+        }
+
+        for (code, meaning) in &self.exit_codes {
+            Self::fill_exit_code_line(&mut expander, *code, meaning);
+        }
+
+        for (name, url) in &self.links {
+            Self::fill_link_line(&mut expander, name, url);
+        }
+
+        if self.templates.contains_key("toc") {
+            self.fill_toc_lines(&mut expander);
+        }
+
+        self.overrides.apply(&mut expander);
+        expander
+    }
+
+    /// Fill the `subcommand-tree-lines` sub-loop with the subcommands
+    /// of the command, then recursively with theirs, `prefix` being
+    /// the branches of the ancestors
+    fn fill_subcommand_tree(
+        expander: &mut OwningTemplateExpander<'static>,
+        cmd: &Command,
+        prefix: &str,
+        depth: usize,
+    ) {
+        if depth == 0 {
+            return;
+        }
+        let subcommands: Vec<&Command> = cmd
+            .get_subcommands()
+            // the help subcommands of the nested levels are just noise
+            .filter(|sc| !sc.is_hide_set() && (prefix.is_empty() || sc.get_name() != "help"))
+            .collect();
+        for (i, subcommand) in subcommands.iter().enumerate() {
+            let last = i + 1 == subcommands.len();
+            let sub = expander.sub("subcommand-tree-lines");
+            sub.set(
+                "branch",
+                format!("{prefix}{}", if last { "└─ " } else { "├─ " }),
+            );
+            sub.set("name", subcommand.get_name());
+            if let Some(about) = subcommand.get_about() {
+                sub.set_md("help", about.to_string());
+            }
+            if subcommand.get_name() != "help" {
+                let prefix = format!("{prefix}{}", if last { "   " } else { "│  " });
+                Self::fill_subcommand_tree(expander, subcommand, &prefix, depth - 1);
+            }
+        }
+    }
+
+    fn fill_keybinding_line(
+        expander: &mut OwningTemplateExpander<'static>,
+        (keys, action, description): &(String, String, String),
+    ) {
+        let sub = expander.sub("keybinding-lines");
+        sub.set("keys", keys);
+        sub.set("action", action);
+        sub.set_md("description", description);
+    }
+
+    fn fill_file_line(
+        expander: &mut OwningTemplateExpander<'static>,
+        path: &Path,
+        description: &str,
+    ) {
+        let home = home_dir();
+        let expanded = match (path.strip_prefix("~"), &home) {
+            (Ok(relative), Some(home)) => home.join(relative),
+            _ => path.to_path_buf(),
+        };
+        let displayed = match home
+            .as_ref()
+            .and_then(|home| expanded.strip_prefix(home).ok())
+        {
+            Some(relative) => Path::new("~").join(relative),
+            None => expanded.clone(),
+        };
+        let sub = expander.sub("file-lines");
+        sub.set("path", displayed.display());
+        sub.set("expanded-path", expanded.display());
+        sub.set_md("description", description);
+    }
+
+    /// Fill the `toc-lines` sub-loop with the sections having a heading,
+    /// and the `toc-subcommand-lines` one with the subcommands, recursively
+    fn fill_toc_lines(&self, expander: &mut OwningTemplateExpander<'static>) {
+        for key in &self.template_keys {
+            let Some((_, label)) = SECTION_HEADINGS.iter().find(|(k, _)| k == key) else {
+                continue;
+            };
+            if !self.templates.contains_key(key) || !self.level.shows_section(key) {
+                continue;
+            }
+            let sub = expander.sub("toc-lines");
+            sub.set("title", self.label(label).trim_end_matches(':'));
+            sub.set("anchor", format_args!("section-{key}"));
+        }
+        fn fill_subcommands(
+            expander: &mut OwningTemplateExpander<'static>,
+            cmd: &Command,
+            parent_path: &str,
+        ) {
+            for subcommand in cmd.get_subcommands() {
+                if subcommand.is_hide_set() || subcommand.get_name() == "help" {
+                    continue;
+                }
+                let path = format!("{parent_path}{} ", subcommand.get_name());
+                expander
+                    .sub("toc-subcommand-lines")
+                    .set("title", path.trim_end());
+                fill_subcommands(expander, subcommand, &path);
+            }
+        }
+        let name = self
+            .cmd
+            .get_bin_name()
+            .unwrap_or_else(|| self.cmd.get_name());
+        fill_subcommands(expander, &self.cmd, &format!("{name} "));
+    }
+
+    fn fill_exit_code_line(
+        expander: &mut OwningTemplateExpander<'static>,
+        code: i32,
+        meaning: &str,
+    ) {
+        let sub = expander.sub("exit-code-lines");
+        sub.set("code", code);
+        sub.set_md("meaning", meaning);
+    }
+
+    /// Fill the `subcommand-group-lines` sub-loop with a row per
+    /// category, followed by the rows of its subcommands
+    fn fill_subcommand_group_lines(&self, expander: &mut OwningTemplateExpander<'static>) {
+        let mut subcommands: Vec<(&str, String)> = self
+            .cmd
+            .get_subcommands()
+            .filter(|s| !s.is_hide_set())
+            .map(|s| {
+                let about = s.get_about().map(|a| a.to_string()).unwrap_or_default();
+                (s.get_name(), about)
+            })
+            .collect();
+        for (name, about) in &self.extra_subcommands {
+            subcommands.push((name, about.clone()));
+        }
+        let category_of = |name: &str| {
+            self.subcommand_categories
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, category)| category.as_str())
+        };
+        let mut categories: Vec<Option<&str>> = Vec::new();
+        for (_, category) in &self.subcommand_categories {
+            if !categories.contains(&Some(category.as_str())) {
+                categories.push(Some(category));
+            }
+        }
+        categories.push(None);
+        for category in categories {
+            let mut rows = subcommands
+                .iter()
+                .filter(|(name, _)| category_of(name) == category)
+                .peekable();
+            if rows.peek().is_none() {
+                continue;
+            }
+            if category.is_some() || !self.subcommand_categories.is_empty() {
+                let title = category.unwrap_or_else(|| self.label("other-commands-label"));
+                expander
+                    .sub("subcommand-group-lines")
+                    .set_md("category", format!("*{title}*"));
+            }
+            for (name, about) in rows {
+                let sub = expander.sub("subcommand-group-lines");
+                sub.set("name", *name);
+                sub.set_md("name-styled", format!("  **{name}**"));
+                sub.set_md("help", self.help_md(about));
+            }
+        }
+    }
+
+    fn fill_link_line(expander: &mut OwningTemplateExpander<'static>, name: &str, url: &str) {
+        let sub = expander.sub("link-lines");
+        sub.set("label", name);
+        sub.set("url", url);
+    }
+
+    /// Build the parts of the usage line of the command following the
+    /// `[options]`: the groups of mutually exclusive arguments, and
+    /// the positional arguments, ending with `[COMMAND]` when the
+    /// command has subcommands or when `with_command` is set
+    fn usage_parts(&self, cmd: &Command, with_command: bool) -> (UsageLine, UsageLine) {
+        let mut args = UsageLine::default();
+        for arg in cmd.get_positionals() {
+            let Some(key) = arg.get_value_names().and_then(|arr| arr.first()) else {
+                continue;
+            };
+
+            args.push_plain(" ");
+
+            if !arg.is_required_set() {
+                args.push(UsageToken::Bracket, "[");
+            }
+
+            // raw arguments are shown as `[-- <ARGS>...]`
+            if arg.is_last_set() {
+                args.push(UsageToken::Flag, "--");
+                args.push_plain(" ");
+                args.push(UsageToken::Bracket, &self.value_brackets.0);
+                args.push(UsageToken::Metavar, key);
+                args.push(UsageToken::Bracket, &self.value_brackets.1);
+            } else {
+                args.push(UsageToken::Metavar, key);
+            }
+
+            if let Some(default) = self.formatted_default(arg) {
+                args.push_plain(&format!("={default}"));
+            }
+
+            if arg.is_last_set() && takes_several_values(arg) {
+                args.push(UsageToken::Bracket, "...");
+            }
+
+            if !arg.is_required_set() {
+                args.push(UsageToken::Bracket, "]");
+            }
+
+            // variadic arguments are shown as `[ARGS]...`
+            if !arg.is_last_set() && takes_several_values(arg) {
+                args.push(UsageToken::Bracket, "...");
+            }
+        }
+
+        if cmd.has_subcommands() || with_command {
+            args.push_plain(" ");
+            args.push(UsageToken::Bracket, "[");
+            args.push(UsageToken::Metavar, "COMMAND");
+            args.push(UsageToken::Bracket, "]");
+        }
+
+        // the groups of mutually exclusive options, eg `[--json | --yaml]`,
+        // or `(--json | --yaml)` when one of them is required. The
+        // positional members are already in the usage.
+        let mut groups = UsageLine::default();
+        for group in cmd.get_groups() {
+            if group.clone().is_multiple() {
+                continue;
+            }
+            let flags: Vec<String> = group
+                .get_args()
+                .filter_map(|id| cmd.get_arguments().find(|a| a.get_id() == id))
+                .filter(|a| !a.is_positional() && !a.is_hide_set() && self.shows_arg(a))
+                .filter_map(|a| {
+                    a.get_long()
+                        .map(|long| format!("--{long}"))
+                        .or_else(|| a.get_short().map(|short| format!("-{short}")))
+                })
+                .collect();
+            if flags.len() < 2 {
+                continue;
+            }
+            let (open, close) = if group.is_required_set() {
+                ("(", ")")
+            } else {
+                ("[", "]")
+            };
+            groups.push_plain(" ");
+            groups.push(UsageToken::Bracket, open);
+            for (i, flag) in flags.iter().enumerate() {
+                if i > 0 {
+                    groups.push_plain(" ");
+                    groups.push(UsageToken::Bracket, "|");
+                    groups.push_plain(" ");
+                }
+                groups.push(UsageToken::Flag, flag);
+            }
+            groups.push(UsageToken::Bracket, close);
+        }
+        (groups, args)
+    }
+
+    /// Build the one-line usage of a subcommand, eg
+    /// `add [options] <NAME> <URL>`
+    fn sub_usage(&self, subcommand: &Command) -> String {
+        let has_options = subcommand.get_arguments().any(|a| {
+            !a.is_positional()
+                && !a.is_hide_set()
+                && !matches!(
+                    a.get_action(),
+                    ArgAction::Help
+                        | ArgAction::HelpShort
+                        | ArgAction::HelpLong
+                        | ArgAction::Version
+                )
+        });
+        let (groups, args) = self.usage_parts(subcommand, false);
+        format!(
+            "{}{}{}{}",
+            subcommand.get_name(),
+            if has_options { " [options]" } else { "" },
+            groups.plain(),
+            args.plain(),
+        )
+    }
+
+    /// The expander of a section added with [Self::add_section], with
+    /// the variables describing the command and the ones of the section
+    fn section_expander(&self, key: &str) -> Option<OwningTemplateExpander<'static>> {
+        let (_, vars) = self.section_vars.iter().find(|(k, _)| *k == key)?;
+        let mut expander = self.base_expander();
+        for (name, value) in vars {
+            expander.set_md(name, value.as_str());
+        }
+        Some(expander)
+    }
+
+    /// Build an expander with the variables describing the command
+    /// as a whole, the labels, and the links
+    fn base_expander(&self) -> OwningTemplateExpander<'static> {
+        let mut expander = OwningTemplateExpander::new();
+        expander.set_default("");
+        let cmd = &self.cmd;
+        let name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+        expander.set("name", name);
+        if cmd.is_allow_external_subcommands_set() {
+            expander.set_md(
+                "external-subcommands-note",
+                format!(
+                    "*{}* `{}-<name>` {}",
+                    self.label("plugins-label"),
+                    name,
+                    self.label("plugins-on-path-label"),
+                ),
+            );
+        }
+
+        if let Some(author) = cmd.get_author() {
+            expander.set("author", author);
+        }
+
+        if let Some(version) = cmd.get_version() {
+            expander.set("version", version);
+        }
+
+        // like clap, the long texts default to the short ones
+        let before_help = cmd.get_before_help().map(|t| t.to_string());
+        let before_long_help = cmd.get_before_long_help().map(|t| t.to_string());
+        if let Some(text) = &before_help {
+            expander.set_md("before_help", text);
+        }
+        if let Some(text) = before_long_help.as_ref().or(before_help.as_ref()) {
+            expander.set_md("before_long_help", text);
+        }
+        let after_help = cmd.get_after_help().map(|t| t.to_string());
+        let after_long_help = cmd.get_after_long_help().map(|t| t.to_string());
+        if let Some(text) = &after_help {
+            expander.set_md("after_help", text);
+        }
+        if let Some(text) = after_long_help.as_ref().or(after_help.as_ref()) {
+            expander.set_md("after_long_help", text);
+        }
+        // the sections need one line per line of the text of the level
+        let texts = match self.level {
+            HelpLevel::Short => [before_help, after_help],
+            _ => [
+                before_long_help.or(before_help),
+                after_long_help.or(after_help),
+            ],
+        };
+        for (sub_loop, text) in ["before-help-lines", "after-help-lines"].iter().zip(texts) {
+            for line in text.iter().flat_map(|text| text.lines()) {
+                expander.sub(sub_loop).set_md("line", line);
+            }
+        }
+
+        let (groups, args) = self.usage_parts(cmd, !self.extra_subcommands.is_empty());
+
+        let mut usage = UsageLine::default();
+        usage.push(UsageToken::Program, name);
+        usage.push_plain(" ");
+        usage.push(UsageToken::Bracket, "[");
+        usage.push(UsageToken::Flag, "options");
+        usage.push(UsageToken::Bracket, "]");
+        expander.set(
+            "usage-styled",
+            format!("{}{}{}", usage.marked(), groups.marked(), args.marked()),
+        );
+        expander.set("usage-groups", groups.plain());
+        expander.set("positional-args", args.plain());
+
+        let option_count = self.shown_options().len();
+        let subcommand_count = cmd.get_subcommands().filter(|s| !s.is_hide_set()).count()
+            + self.extra_subcommands.len();
+        let positional_count = cmd.get_positionals().filter(|a| self.shows_arg(a)).count();
+        expander.set("option-count", option_count);
+        expander.set("subcommand-count", subcommand_count);
+        expander.set("positional-count", positional_count);
+        let counts = [
+            (option_count, "option-label", "options-plural-label"),
+            (
+                subcommand_count,
+                "subcommand-label",
+                "subcommands-plural-label",
+            ),
+            (
+                positional_count,
+                "positional-label",
+                "positionals-plural-label",
+            ),
+        ];
+        let summary: Vec<String> = counts
+            .iter()
+            .filter(|(count, _, _)| *count > 0)
+            .map(|&(count, singular, plural)| {
+                let label = if count == 1 { singular } else { plural };
+                format!("{count} {}", self.label(label))
+            })
+            .collect();
+        expander.set("summary", summary.join(", "));
+
+        for (key, text) in self.locale.labels() {
+            expander.set(key, text);
+        }
+        for (name, url) in &self.links {
+            expander.set(name, url);
+        }
+        expander
+    }
+
+    /// Set the variables of an element of the `option-lines` sub-loop
+    fn option_line(&self, arg: &Arg, highlighted: bool) -> LineValues {
+        let (help, deprecation) = self.help_and_deprecation(arg);
+        let mut sub = LineValues::new();
+
+        sub.set("id", arg.get_id());
+        sub.set("anchor", arg_anchor(arg));
+
+        // the marker of a highlighted option is put before its first name
+        let mut marker = if highlighted { HIGHLIGHT_MARKER } else { "" };
+        // and the badges after its last name
+        let badges = self.badges_md(arg);
+
+        let (short, long) = self.option_names(arg);
+
+        if let Some(short) = short {
+            let badges = if long.is_none() { &badges } else { "" };
+            match deprecation {
+                Some(_) => sub.set_md("short", format!("{marker}~~{short}~~{badges}")),
+                None if highlighted || !badges.is_empty() => {
+                    sub.set_md("short", format!("{marker}{short}{badges}"))
+                }
+                None => sub.set("short", short),
+            };
+            marker = "";
+        }
+
+        if let Some(long) = long {
+            match deprecation {
+                Some(_) => sub.set_md("long", format!("{marker}~~{long}~~{badges}")),
+                None if !marker.is_empty() || !badges.is_empty() => {
+                    sub.set_md("long", format!("{marker}{long}{badges}"))
+                }
+                None => sub.set("long", long),
+            };
+        }
+
+        match self.option_styler {
+            Some(_) => {
+                // the marker lets the lines of the option be found in the rendered text
+                let idx = self
+                    .cmd
+                    .get_arguments()
+                    .position(|a| a.get_id() == arg.get_id());
+                let marker = row_marker(idx.unwrap_or(usize::MAX));
+                sub.set_md("help", format!("{marker}{}", self.help_md(&help)));
+            }
+            None => sub.set_md("help", self.help_md(&help)),
+        }
+
+        if let Some(note) = deprecation {
+            sub.set_md(
+                "deprecated-note",
+                format!(" *{}* {}", self.label("deprecated-label"), note),
+            );
+        }
+
+        if arg.get_action().takes_values() {
+            let names = arg.get_value_names().unwrap_or_default();
+            if !names.is_empty() {
+                // all the value names, eg `FROM TO` for `--map <FROM> <TO>`
+                let name = join_names(names, "", " ", "");
+                sub.set("value", &name);
+                sub.set("value-names", join_names(names, "", ", ", ""));
+                match ValueType::of(arg) {
+                    Some(value_type) => {
+                        sub.set("value-type", value_type.name());
+                        let mut styled = String::new();
+                        for (i, name) in names.iter().enumerate() {
+                            if i > 0 {
+                                styled.push(' ');
+                            }
+                            styled.push_str(&value_type.styled(name));
+                        }
+                        sub.set_md("value-styled", styled);
+                    }
+                    None => {
+                        sub.set("value-styled", &name);
+                    }
+                }
+                sub.set("value-bare", &name);
+                let (open, close) = &self.value_brackets;
+                let braced = join_names(names, open, " ", close);
+                sub.set("value-braced", &braced);
+
+                if arg.get_short().is_some() {
+                    sub.set("value-short-braced", &braced);
+                    sub.set("value-short", &name);
+                }
+
+                if arg.get_long().is_some() {
+                    sub.set("value-long-braced", &braced);
+                    sub.set("value-long", &name);
+                }
+            }
+        }
+
+        self.fill_arg_details(&mut sub, arg);
+
+        let range = self
+            .value_ranges
+            .iter()
+            .find(|(id, _)| arg.get_id() == id.as_str())
+            .map(|(_, range)| Cow::Borrowed(range.as_str()))
+            .or_else(|| value_range(arg).map(Cow::Owned));
+        if let Some(range) = range {
+            sub.set_md(
+                "details-constraints",
+                format!(" {} {}", self.label("range-label"), self.value_md(&range)),
+            );
+            sub.set("value-range", range);
+        }
+
+        if let Some((value, source)) = self.current_value(arg) {
+            sub.set_md(
+                "current",
+                format!(
+                    " *{}* {} *({})*",
+                    self.label("current-label"),
+                    self.value_md(&value),
+                    self.label(source),
+                ),
+            );
+            sub.set("current-value", value);
+        }
+        sub
+    }
+
+    /// Return the value in effect for the argument, as given with
+    /// [Self::with_matches], and the label of its source
+    fn current_value(&self, arg: &Arg) -> Option<(String, &'static str)> {
+        let (_, values, source) = self
+            .current_values
+            .iter()
+            .find(|(id, _, _)| arg.get_id() == id.as_str())?;
+        if *source == "env-source-label" && arg.is_hide_env_values_set() {
+            return Some(("***".to_string(), source));
+        }
+        let values: Vec<String> = match &self.default_formatter {
+            Some(f) => values
+                .iter()
+                .map(|value| f(OsStr::new(value), arg))
+                .collect::<Option<_>>()?,
+            None => values.clone(),
+        };
+        Some((values.join(", "), source))
+    }
+
+    /// Set the `required`, `possible_values` and `default` variables,
+    /// which are common to options and positional arguments
+    fn fill_arg_details(&self, sub: &mut LineValues, arg: &Arg) {
+        if let Some(note) = self.gate_note(arg) {
+            sub.set_md("gate-note", note);
+        }
+
+        if let Some(hint) = self.repeat_hint(arg) {
+            sub.set_md("repeat-hint", hint);
+        }
+
+        if let Some(levels) = self.count_levels_md(arg) {
+            sub.set_md("count-levels", levels);
+        }
+
+        if self.accessibility == AccessibilityMode::TextMarkers && arg.is_required_set() {
+            sub.set_md("required", format!(" *({})*", self.label("required-label")));
+        }
+
+        let possible_values = arg.get_possible_values();
+
+        if !possible_values.is_empty() {
+            let max = self.max_possible_values.unwrap_or(usize::MAX);
+            let mut md = format!(" {} [", self.label("possible-values-label"));
+            for (i, value) in possible_values.iter().take(max).enumerate() {
+                if i > 0 {
+                    md.push_str(", ");
+                }
+                md.push_str(&self.value_md(value.get_name()));
+            }
+            if possible_values.len() > max {
+                md.push_str(&format!(
+                    ", … {} {} {}",
+                    self.label("and-label"),
+                    possible_values.len() - max,
+                    self.label("more-label"),
+                ));
+            }
+            md.push(']');
+            sub.set_md("possible_values", md);
+        }
+
+        if let Some(default) = self.formatted_default(arg) {
+            sub.set_md(
+                "default",
+                format!(
+                    " {} {}",
+                    self.label("default-label"),
+                    self.value_md(&default),
+                ),
+            );
+        }
+    }
+
+    /// Return the hint telling a counted flag, eg `-v` for `-vvv`,
+    /// may be repeated
+    fn repeat_hint(&self, arg: &Arg) -> Option<String> {
+        matches!(arg.get_action(), ArgAction::Count)
+            .then(|| format!(" *({})*", self.label("repeat-label")))
+    }
+
+    /// Return the description of the levels of a counted flag, as
+    /// given with [Self::with_count_levels]
+    fn count_levels_md(&self, arg: &Arg) -> Option<String> {
+        let (_, levels) = self
+            .count_levels
+            .iter()
+            .find(|(id, _)| arg.get_id() == id.as_str())?;
+        let mut md = format!(" *{}*", self.label("levels-label"));
+        for (i, level) in levels.iter().enumerate() {
+            let flag = match (arg.get_short(), arg.get_long()) {
+                (Some(short), _) => format!("-{}", short.to_string().repeat(i + 1)),
+                (None, Some(long)) => vec![format!("--{long}"); i + 1].join(" "),
+                (None, None) => continue,
+            };
+            if i > 0 {
+                md.push(',');
+            }
+            md.push_str(&format!(" `{flag}` {level}"));
+        }
+        Some(md)
+    }
+
+    /// The default value of an option, as shown in the help
+    fn formatted_default(&self, arg: &Arg) -> Option<String> {
+        if !matches!(arg.get_action(), ArgAction::Set | ArgAction::Append) {
+            return None;
+        }
+        let default = arg.get_default_values().first()?;
+        match &self.default_formatter {
+            Some(f) => f(default, arg),
+            None => Some(default.to_string_lossy().to_string()),
+        }
+    }
+
+    /// Add an element to the `possible-value-lines` sub-loop for each
+    /// visible possible value of the option, the name of the option
+    /// being only set on the first one
+    fn fill_possible_value_lines(&self, expander: &mut OwningTemplateExpander<'static>, arg: &Arg) {
+        let possible_values = arg.get_possible_values();
+        let possible_values = possible_values.iter().filter(|v| !v.is_hide_set());
+        for (idx, possible_value) in possible_values.enumerate() {
+            let sub = expander.sub("possible-value-lines");
+            if idx == 0 {
+                match (arg.get_long(), arg.get_short()) {
+                    (Some(long), _) => sub.set("option", format!("--{long}")),
+                    (None, Some(short)) => sub.set("option", format!("-{short}")),
+                    (None, None) => sub.set("option", arg.get_id()),
+                };
+            }
+            sub.set_md("value", self.value_md(possible_value.get_name()));
+            if let Some(help) = possible_value.get_help() {
+                sub.set_md("help", self.help_md(&help.to_string()));
+            }
+        }
+    }
+
+    /// Fill a sub-loop whose elements are rows of `columns` flags
+    fn fill_flag_rows(
+        &self,
+        expander: &mut OwningTemplateExpander<'static>,
+        sub_loop: &'static str,
+        flags: &[&Arg],
+        columns: usize,
+    ) {
+        const FLAG_NAMES: [&str; 3] = ["flag-1", "flag-2", "flag-3"];
+        const HELP_NAMES: [&str; 3] = ["help-1", "help-2", "help-3"];
+        for row in flags.chunks(columns) {
+            let sub = expander.sub(sub_loop);
+            for (i, arg) in row.iter().enumerate() {
+                let Some(flag) = self.flag_name(arg) else {
+                    continue;
+                };
+                let (help, deprecation) = self.help_and_deprecation(arg);
+                let badges = self.badges_md(arg);
+                // the details of a flag are appended to its help
+                let mut details = String::new();
+                for detail in [
+                    self.repeat_hint(arg),
+                    self.count_levels_md(arg),
+                    self.gate_note(arg),
+                ] {
+                    details.push_str(&detail.unwrap_or_default());
+                }
+                match deprecation {
+                    Some(note) => {
+                        sub.set_md(FLAG_NAMES[i], format!("~~{flag}~~{badges}"));
+                        sub.set_md(
+                            HELP_NAMES[i],
+                            format!(
+                                "{} *{}* {}{details}",
+                                self.help_md(&help),
+                                self.label("deprecated-label"),
+                                note,
+                            ),
+                        );
+                    }
+                    None => {
+                        if badges.is_empty() {
+                            sub.set(FLAG_NAMES[i], flag);
+                        } else {
+                            sub.set_md(FLAG_NAMES[i], format!("{flag}{badges}"));
+                        }
+                        sub.set_md(HELP_NAMES[i], format!("{}{details}", self.help_md(&help)));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Return the badges of the argument, as markdown
+    fn badges_md(&self, arg: &Arg) -> String {
+        let mut md = String::new();
+        for (idx, (id, badge)) in self.badges.iter().enumerate() {
+            if arg.get_id() == id.as_str() {
+                md.push_str(&badge.md(idx));
+            }
+        }
+        md
+    }
+
+    /// Return the legend of the badges of the options, each badge
+    /// being listed once, with its description
+    fn badge_legend(&self, options: &[&Arg]) -> Option<String> {
+        let mut texts: Vec<&str> = Vec::new();
+        let mut legend = Vec::new();
+        for (idx, (id, badge)) in self.badges.iter().enumerate() {
+            if texts.contains(&badge.text.as_str())
+                || !options.iter().any(|arg| arg.get_id() == id.as_str())
+            {
+                continue;
+            }
+            texts.push(&badge.text);
+            let md = badge.md(idx);
+            legend.push(match &badge.description {
+                Some(description) => format!("{} {description}", md.trim_start()),
+                None => md.trim_start().to_string(),
+            });
+        }
+        (!legend.is_empty()).then(|| legend.join(", "))
+    }
+
+    /// Return the help of the argument and, when it's deprecated, the
+    /// deprecation note, either given to [Self::mark_deprecated] or
+    /// found at the start of the help
+    fn help_and_deprecation(&self, arg: &Arg) -> (String, Option<String>) {
+        let help = arg.get_help().map(|h| h.to_string()).unwrap_or_default();
+        let marked = self
+            .deprecated
+            .iter()
+            .find(|(id, _)| arg.get_id() == id.as_str())
+            .map(|(_, note)| note.clone());
+        match parse_deprecated_help(&help) {
+            Some((note, rest)) => (rest.to_string(), marked.or_else(|| Some(note.to_string()))),
+            None => (help, marked),
+        }
+    }
+
+    /// Return the markdown of a help string, with its references to
+    /// options and subcommands resolved
+    fn help_md(&self, help: &str) -> String {
+        let help = resolve_references(help, &self.cmd);
+        if !self.glossary_highlighting {
+            return help.into_owned();
+        }
+        italicize_terms(&help, &self.glossary).into_owned()
+    }
+
+    /// Return the markdown of a value, eg a default value, which is
+    /// quoted when the style mustn't be the only marker
+    fn value_md(&self, value: &str) -> String {
+        match self.accessibility {
+            AccessibilityMode::Standard => format!("`{value}`"),
+            AccessibilityMode::TextMarkers => format!("\"{value}\""),
+        }
+    }
+
+    /// Return the text of a label of the locale
+    fn label(&self, key: &str) -> &str {
+        self.locale.get(key).unwrap_or_default()
+    }
+
+    /// Find an option by its id, its long name (eg `--format` or `format`),
+    /// or its short name (eg `-f`)
+    fn find_option(&self, name: &str) -> Option<&Arg> {
+        let long = name.strip_prefix("--").unwrap_or(name);
+        let short = name
+            .strip_prefix('-')
+            .filter(|s| s.chars().count() == 1)
+            .and_then(|s| s.chars().next());
+        self.cmd.get_arguments().find(|a| {
+            a.get_id() == name
+                || a.get_long() == Some(long)
+                || (short.is_some() && a.get_short() == short)
+        })
+    }
+
+    /// Give you a mut reference to the expander, so that you can overload
+    /// the variable of the expander used to fill the templates of the help,
+    /// or add new variables for your own templates
+    ///
+    /// The expander is built again when a setting it reads is changed,
+    /// eg with [Self::with_max_width] or [Self::add_section], so the
+    /// variables set here must be set after all settings. Use
+    /// [Self::overrides_mut] to set variables which are kept.
+    pub fn expander_mut(&mut self) -> &mut OwningTemplateExpander<'static> {
+        self.expander();
+        self.expander
+            .get_mut()
+            .expect("the expander was just built")
+    }
+
+    /// Give you a mut reference to the variables you set over the ones
+    /// of the expander used to fill the templates of the help, so that
+    /// you can overload them, or add new variables for your own templates
+    ///
+    /// They're applied after the variables of the printer every time the
+    /// expander is built, so they're kept whatever the settings changed
+    /// afterwards.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let mut printer = clap_help::Printer::new(Args::command());
+    /// printer.overrides_mut().set("name", "my-tool");
+    /// let printer = printer.with_max_width(100);
+    /// let help = printer.render_for_test(80, Default::default());
+    /// assert!(help.contains("my-tool"));
+    /// ```
+    pub fn overrides_mut(&mut self) -> &mut ExpanderOverrides {
+        self.reset_expander();
+        &mut self.overrides
+    }
+
+    /// The expander, built on first use
+    fn expander(&self) -> &OwningTemplateExpander<'static> {
+        self.expander.get_or_init(|| self.make_expander(None, &[]))
+    }
+
+    /// Drop the expander, so that it's built again with the current
+    /// settings on next use.
+    ///
+    /// Every setting changing a variable, a sub-loop, or the sections
+    /// listed in the table of contents must call it. The variables set
+    /// with [Self::overrides_mut] aren't lost, they're applied again,
+    /// unlike the ones set with [Self::expander_mut].
+    fn reset_expander(&mut self) {
+        self.expander = OnceLock::new();
+    }
+
+    /// Describe the variables and sub-loops filled by the expander,
+    /// which you can use in your own templates
+    pub fn available_variables(&self) -> Vec<VariableInfo> {
+        let mut variables = BUILTIN_VARIABLES.to_vec();
+        // all the option sub-loops have the same variables than option-lines
+        let option_variables: Vec<VariableInfo> = OPTION_SUB_LOOPS
+            .iter()
+            .filter(|&&sub_loop| sub_loop != "option-lines")
+            .flat_map(|&sub_loop| {
+                variables
+                    .iter()
+                    .filter(|v| v.scope == VariableScope::SubLoop("option-lines"))
+                    .map(move |v| VariableInfo {
+                        scope: VariableScope::SubLoop(sub_loop),
+                        ..v.clone()
+                    })
+            })
+            .collect();
+        variables.extend(option_variables);
+        variables.extend(self.locale.labels().map(|(key, text)| VariableInfo {
+            example: text.to_string().into(),
+            ..VariableInfo::global(key, "")
+        }));
+        variables.extend(self.declared_variables.iter().cloned());
+        variables
+    }
 
-        // they say it's the hackiest solution of all time
-        if !cmd
-            .clone()
-            .get_arguments()
-            .filter(|a| !a.is_hide_set())
-            .filter(|a| a.get_short().is_some() || a.get_long().is_some())
-            .collect::<Vec<_>>()
-            .is_empty()
-        {
-            for arg in options {
-                let sub = expander.sub("option-lines");
+    /// Declare a variable you set yourself with [Self::expander_mut],
+    /// so that it's known to [Self::check_templates]
+    pub fn declare_variable(&mut self, variable: VariableInfo) {
+        self.declared_variables.push(variable);
+    }
 
-                if let Some(short) = arg.get_short() {
-                    sub.set("short", format!("-{short}"));
+    /// Check the references to options (`{--format}` or `{-f}`) and to
+    /// subcommands (`[[subcmd:build]]`) in the help strings, and report
+    /// the ones which don't exist, eg in a test of your application.
+    ///
+    /// ```
+    /// use clap::{Arg, Command};
+    ///
+    /// let cmd = Command::new("app")
+    ///     .arg(Arg::new("format").long("format").help("Output format"))
+    ///     .arg(Arg::new("raw").long("raw").help("Same as {--format} raw, see {--fromat}"));
+    /// let broken = clap_help::Printer::new(cmd).check_references();
+    /// assert_eq!(broken.len(), 1);
+    /// assert_eq!(broken[0].reference, "{--fromat}");
+    /// ```
+    pub fn check_references(&self) -> Vec<BrokenReference> {
+        let cmd = &self.cmd;
+        let mut broken = Vec::new();
+        for arg in cmd.get_arguments() {
+            let location = match (arg.get_long(), arg.get_short()) {
+                (Some(long), _) => format!("--{long}"),
+                (None, Some(short)) => format!("-{short}"),
+                (None, None) => arg.get_id().to_string(),
+            };
+            if let Some(help) = arg.get_help() {
+                check_references(&location, &help.to_string(), cmd, &mut broken);
+            }
+            for value in arg.get_possible_values() {
+                if let Some(help) = value.get_help() {
+                    let location = format!("{location} {}", value.get_name());
+                    check_references(&location, &help.to_string(), cmd, &mut broken);
                 }
+            }
+        }
+        for subcommand in cmd.get_subcommands() {
+            if let Some(about) = subcommand.get_about() {
+                check_references(subcommand.get_name(), &about.to_string(), cmd, &mut broken);
+            }
+        }
+        for (name, about) in &self.extra_subcommands {
+            check_references(name, about, cmd, &mut broken);
+        }
+        broken
+    }
 
-                if let Some(long) = arg.get_long() {
-                    sub.set("long", format!("--{long}"));
+    /// Check the installed templates, and report the placeholders
+    /// the expander will never fill, the unknown sub-loops and
+    /// conditions, and the custom sections of the section order
+    /// which have no template.
+    ///
+    /// Variables you set with [Self::expander_mut] are reported
+    /// unless you declared them with [Self::declare_variable].
+    pub fn check_templates(&self) -> Vec<TemplateIssue> {
+        let variables = self.available_variables();
+        let conditions = self.known_conditions();
+        let mut issues = Vec::new();
+        let is_installed = |key: &&'static str| {
+            self.templates.contains_key(key) || self.responsive_templates.contains_key(key)
+        };
+        // the built-in sections may lack a template, eg "positionals"
+        // for a command without positional argument, but not custom ones
+        for key in &self.template_keys {
+            if !is_installed(key) && !Section::from(*key).is_builtin() {
+                issues.push(TemplateIssue {
+                    key,
+                    line: 0,
+                    kind: TemplateIssueKind::MissingTemplate,
+                });
+            }
+        }
+        let mut keys: Vec<&'static str> = self
+            .template_keys
+            .iter()
+            .copied()
+            .filter(is_installed)
+            .collect();
+        let mut other_keys: Vec<&'static str> = self
+            .templates
+            .keys()
+            .chain(self.responsive_templates.keys())
+            .copied()
+            .filter(|key| !keys.contains(key))
+            .collect();
+        other_keys.sort_unstable();
+        other_keys.dedup();
+        keys.extend(other_keys);
+        for key in keys {
+            if let Some(templates) = self.responsive_templates.get(key) {
+                for (_, template) in templates {
+                    let template = expand_partials(template, |name| self.partial(name));
+                    check_template(key, &template, &variables, &conditions, &mut issues);
                 }
+            } else if let Some(template) = self.templates.get(key) {
+                let template = expand_partials(template, |name| self.partial(name));
+                check_template(key, &template, &variables, &conditions, &mut issues);
+            }
+        }
+        // the variables given to add_section are known in their section
+        issues.retain(|issue| match &issue.kind {
+            TemplateIssueKind::UnknownVariable {
+                name,
+                sub_loop: None,
+            } => !self
+                .section_vars
+                .iter()
+                .any(|(key, vars)| *key == issue.key && vars.iter().any(|(n, _)| n == name)),
+            _ => true,
+        });
+        issues
+    }
 
-                if let Some(help) = arg.get_help() {
-                    sub.set_md("help", help.to_string());
-                }
+    /// Print the provided template with the printer's expander
+    ///
+    /// It's normally more convenient to change template_keys or some
+    /// templates, unless you want none of the standard templates
+    pub fn print_template(&self, template: &str) {
+        let _ = self.write_template(template);
+    }
 
-                if arg.get_action().takes_values() {
-                    if let Some(name) = arg.get_value_names().and_then(|arr| arr.first()) {
-                        sub.set("value", name);
-                        let braced = format!("<{}>", name);
-                        sub.set("value-braced", &braced);
+    /// Print the provided template with the printer's expander, after
+    /// having checked it, and report the invalid template or the
+    /// failure to write instead of ignoring them.
+    ///
+    /// Unknown variables and sub-loops aren't errors, as they may
+    /// be set with [Self::expander_mut].
+    pub fn try_print_template(&self, template: &str) -> Result<(), Error> {
+        let mut issues = Vec::new();
+        let template = expand_partials(template, |name| self.partial(name));
+        check_template(
+            "template",
+            &template,
+            &self.available_variables(),
+            &self.known_conditions(),
+            &mut issues,
+        );
+        issues.retain(TemplateIssue::is_error);
+        if !issues.is_empty() {
+            return Err(Error::Template(issues));
+        }
+        self.write_template(&template)?;
+        Ok(())
+    }
 
-                        if arg.get_short().is_some() {
-                            sub.set("value-short-braced", &braced);
-                            sub.set("value-short", name);
-                        }
+    fn write_template(&self, template: &str) -> io::Result<()> {
+        let width = self.terminal_width();
+        let template = self.prepare_template(template, RenderContext::default());
+        let text = self.render(self.expander(), &template, width);
+        self.try_write(&self.text_string(&text))?;
+        self.output().flush()
+    }
 
-                        if arg.get_long().is_some() {
-                            sub.set("value-long-braced", &braced);
-                            sub.set("value-long", name);
-                        }
-                    };
-                }
+    /// Lock the target of the help, which is shared with the
+    /// printers of the subcommands
+    fn output(&self) -> MutexGuard<'_, Output> {
+        self.target.lock().unwrap_or_else(PoisonError::into_inner)
+    }
 
-                let mut possible_values = arg.get_possible_values();
+    /// Write a text to the target, ignoring the errors, eg when the
+    /// pipe was closed
+    fn write(&self, text: &str) {
+        let _ = self.try_write(text);
+    }
 
-                if !possible_values.is_empty() {
-                    let possible_values: Vec<String> = possible_values
-                        .drain(..)
-                        .map(|v| format!("`{}`", v.get_name()))
-                        .collect();
+    fn try_write(&self, text: &str) -> io::Result<()> {
+        self.output().write_all(text.as_bytes())
+    }
 
-                    expander.sub("option-lines").set_md(
-                        "possible_values",
-                        format!(" Possible values: [{}]", possible_values.join(", ")),
-                    );
-                }
+    /// Write a rendered text, with the urls of the links made
+    /// clickable when possible
+    fn text_string(&self, text: &FmtText<'_, '_>) -> String {
+        let rendered = match &self.option_styler {
+            Some(styler) => self.styled_text_string(text, styler),
+            None => text.to_string(),
+        };
+        let rendered = match &self.usage_style {
+            Some(style) => style_usage(&rendered, style),
+            None => rendered,
+        };
+        let rendered = if self.badges.iter().any(|(_, badge)| badge.color.is_some()) {
+            let badges: Vec<&Badge> = self.badges.iter().map(|(_, badge)| badge).collect();
+            strip_markers(&color_badges(&rendered, &badges)).into_owned()
+        } else {
+            strip_markers(&rendered).into_owned()
+        };
+        if self.hyperlinks && !self.links.is_empty() {
+            let urls: Vec<&str> = self.links.iter().map(|(_, url)| url.as_str()).collect();
+            hyperlink_urls(&rendered, &urls)
+        } else {
+            rendered
+        }
+    }
 
-                if let Some(default) = arg.get_default_values().first() {
-                    match arg.get_action() {
-                        ArgAction::Set | ArgAction::Append => {
-                            expander.sub("option-lines").set_md(
-                                "default",
-                                format!(" Default: `{}`", default.to_string_lossy()),
-                            );
-                        }
-                        _ => {}
-                    }
+    /// Write a rendered text, with the lines of the options (found
+    /// with their markers) styled by the option styler
+    fn styled_text_string(&self, text: &FmtText<'_, '_>, styler: &OptionStyler<'t>) -> String {
+        let args: Vec<&Arg> = self.cmd.get_arguments().collect();
+        let mut row_skin: Option<MadSkin> = None;
+        let mut rendered = String::new();
+        for line in &text.lines {
+            match line_marker_index(line) {
+                Some(idx) => {
+                    row_skin = args.get(idx).and_then(|arg| styler(arg)).map(|style| {
+                        let mut skin = text.skin.clone();
+                        style.apply_to_skin(&mut skin);
+                        skin
+                    });
                 }
+                None if !is_continuation(line) => row_skin = None,
+                None => {}
             }
+            let line = SkinnedLine {
+                skin: row_skin.as_ref().unwrap_or(text.skin),
+                line,
+                width: text.width,
+            };
+            rendered.push_str(&format!("{line}\n"));
         }
+        rendered
+    }
 
-        let mut args = String::new();
-        if !cmd.get_positionals().collect::<Vec<_>>().is_empty() {
-            for arg in cmd.get_positionals() {
-                let Some(key) = arg.get_value_names().and_then(|arr| arr.first()) else {
-                    continue;
-                };
+    /// Expand the template and wrap the text for the given width
+    fn render<'s>(
+        &'s self,
+        expander: &'s OwningTemplateExpander<'static>,
+        template: &'s str,
+        width: usize,
+    ) -> FmtText<'s, 's> {
+        let template = TextTemplate::from(template);
+        let mut text = expander.expand(&template);
+        if let Some(table_style) = &self.table_style {
+            table_style.apply_to_text(&mut text);
+        }
+        apply_wrap_policy(&mut text, self.displayed_skin(), width, self.wrap_policy);
+        if self.direction == Direction::RightToLeft {
+            mirror_tables(&mut text);
+        }
+        let mut fmt_text = fmt_text(self.displayed_skin(), text, width);
+        if self.direction == Direction::RightToLeft {
+            reorder_lines(&mut fmt_text);
+        }
+        fmt_text
+    }
 
-                args.push(' ');
+    /// Render only the section of the given key, eg "options" or
+    /// "subcommands", with its current template.
+    ///
+    /// Return an empty string when there's no such section.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[arg(long)]
+    /// #     verbose: bool,
+    /// # }
+    /// let printer = clap_help::Printer::new(Args::command());
+    /// assert!(printer.render_section("options").contains("--verbose"));
+    /// assert!(printer.render_section("exit-codes").is_empty());
+    /// ```
+    pub fn render_section(&self, key: impl AsRef<str>) -> String {
+        let key = key.as_ref();
+        let width = self.help_width();
+        let Some(template) = self.template_for(key, width) else {
+            return String::new();
+        };
+        let template = self.section_template(key, template, RenderContext::default());
+        let section_expander = self.section_expander(key);
+        let expander = section_expander.as_ref().unwrap_or_else(|| self.expander());
+        let text = self.render(expander, &template, width);
+        self.decorated(key, self.text_string(&text))
+    }
 
-                if !arg.is_required_set() {
-                    args.push('[');
-                }
+    /// Print only the section of the given key, eg the options
+    /// after a typo in an interactive prompt.
+    ///
+    /// Return false when there's no such section.
+    pub fn print_section(&self, key: impl AsRef<str>) -> bool {
+        let section = self.render_section(key);
+        self.write(&section);
+        !section.is_empty()
+    }
 
-                if arg.is_last_set() {
-                    args.push_str("-- ");
-                }
+    /// Print the help with a marker before the names of the given
+    /// options, which may be given by their ids, their long names
+    /// (eg `--format`), or their short names (eg `-f`).
+    ///
+    /// This is useful to redirect the user to the relevant part
+    /// of the help after an error.
+    /// Return false when an option isn't found.
+    pub fn print_help_highlighting(&self, names: &[&str]) -> bool {
+        let args: Vec<&Arg> = names
+            .iter()
+            .filter_map(|name| self.find_option(name))
+            .collect();
+        let ids: Vec<&str> = args.iter().map(|arg| arg.get_id().as_str()).collect();
+        self.print_logo();
+        self.write(&self.help_text_with(&self.make_expander(None, &ids), RenderContext::default()));
+        args.len() == names.len()
+    }
 
-                args.push_str(key);
+    /// Print the usage section and the row of the given option,
+    /// which may be given by its id, its long name (eg `--format`),
+    /// or its short name (eg `-f`).
+    ///
+    /// The row is rendered with the current "options" template, so
+    /// in the layout of the help.
+    ///
+    /// This is useful after an error regarding this option.
+    /// Return false, and print nothing, when no option is found or
+    /// when there's no "options" section.
+    pub fn print_option_help(&self, name: &str) -> bool {
+        let Some(arg) = self.find_option(name) else {
+            return false;
+        };
+        let width = self.available_width();
+        let options = self.template_for("options", width).or_else(|| {
+            self.templates
+                .get("options")
+                .map(|template| template.as_ref())
+        });
+        let Some(options) = options else {
+            return false;
+        };
+        let mut expander = self.base_expander();
+        // the option is in the same sub-loops than in the help
+        let line = self.option_line(arg, false);
+        line.fill(expander.sub("option-lines"));
+        if self.is_common(arg) {
+            line.fill(expander.sub("common-option-lines"));
+        } else {
+            line.fill(expander.sub("advanced-option-lines"));
+        }
+        if arg.get_action().takes_values() {
+            line.fill(expander.sub("value-option-lines"));
+        }
+        let usage = self.template_for("usage", width).unwrap_or(TEMPLATE_USAGE);
+        for template in [usage, options] {
+            let template = self.prepare_template(template, RenderContext::default());
+            self.write(&self.text_string(&self.render(&expander, &template, width)));
+        }
+        true
+    }
 
-                if !arg.is_required_set() {
-                    args.push(']');
-                }
+    /// Print a styled error banner with the given message (interpreted
+    /// as markdown), followed by the usage section.
+    ///
+    /// The banner's template can be changed with the "error" key.
+    pub fn print_usage_error(&self, msg: &str) {
+        let mut expander = self.base_expander();
+        expander.set_md("error", msg);
+        let width = self.available_width();
+        let banner = self
+            .templates
+            .get("error")
+            .map_or(TEMPLATE_ERROR, |template| template.as_ref());
+        let banner = self.prepare_template(banner, RenderContext::default());
+        self.write(&self.text_string(&self.render(&expander, &banner, width)));
+        let usage = self.template_for("usage", width).unwrap_or(TEMPLATE_USAGE);
+        let usage = self.prepare_template(usage, RenderContext::default());
+        self.write(&self.text_string(&self.render(&expander, &usage, width)));
+    }
 
-                let sub = expander.sub("positional-lines");
-                sub.set("key", key);
+    /// Build a printer of the errors of clap with the skin
+    /// and the locale of this printer
+    pub fn error_printer(&self) -> ErrorPrinter<'static> {
+        ErrorPrinter::default()
+            .with_skin(self.displayed_skin().clone())
+            .with_locale(self.locale.clone())
+    }
 
-                if let Some(help) = arg.get_help() {
-                    sub.set("help", help);
-                }
+    /// Print all the templates, in order
+    pub fn print_help(&self) {
+        let _ = self.write_help(RenderContext::default());
+    }
+
+    /// Print all the templates, in order, after having checked them,
+    /// and report the invalid templates or the failure to write instead
+    /// of ignoring them.
+    ///
+    /// Unknown variables and sub-loops aren't errors, as they may
+    /// be set with [Self::expander_mut].
+    ///
+    /// ```no_run
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command());
+    /// match printer.try_print_help() {
+    ///     Err(e) if e.is_broken_pipe() => {}
+    ///     Err(e) => eprintln!("{e}"),
+    ///     Ok(()) => {}
+    /// }
+    /// ```
+    pub fn try_print_help(&self) -> Result<(), Error> {
+        let mut issues = self.check_templates();
+        issues.retain(TemplateIssue::is_error);
+        if !issues.is_empty() {
+            return Err(Error::Template(issues));
+        }
+        self.write_help(RenderContext::default())?;
+        Ok(())
+    }
+
+    fn write_help(&self, ctx: RenderContext) -> io::Result<()> {
+        if self.pager && !ctx.help_all && self.output().is_tty() {
+            let help = self.help_text(ctx);
+            if page(&help).is_ok() {
+                return Ok(());
             }
+            self.print_logo();
+            self.try_write(&help)?;
+            return self.output().flush();
+        }
+        self.print_logo();
+        if self.width_strategy == WidthStrategy::TwoPass || self.max_height.is_some() {
+            self.try_write(&self.help_text(ctx))?;
+        } else {
+            self.for_each_section(self.expander(), ctx, |section| self.try_write(&section))?;
         }
+        self.output().flush()
+    }
 
-        if !cmd.get_subcommands().collect::<Vec<_>>().is_empty() {
-            args.push_str(" [COMMAND]");
-            for subcommand in cmd.get_subcommands() {
-                if !subcommand.is_hide_set() {
-                    let sub = expander.sub("subcommand-lines");
-                    sub.set("name", subcommand.get_name());
-                    if let Some(about) = subcommand.get_about() {
-                        sub.set_md("help", about.to_string());
-                    } else {
-                        sub.set("help", "");
-                    }
-                }
+    fn print_logo(&self) {
+        #[cfg(feature = "graphics")]
+        if let Some(logo) = &self.logo {
+            let mut target = self.output();
+            if target.is_tty() {
+                crate::graphics::print_logo(logo, &mut *target);
+            }
+        }
+    }
+
+    /// Write the rendered help, with its styles, to a file of the
+    /// given format, eg an asciicast embeddable in the documentation
+    /// with an asciinema player.
+    ///
+    /// ```no_run
+    /// use clap_help::RecordFormat;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// clap_help::Printer::new(Args::command())
+    ///     .with_fixed_width(100)
+    ///     .record("docs/help.cast", RecordFormat::Asciicast)
+    ///     .unwrap();
+    /// ```
+    pub fn record<P: AsRef<Path>>(&self, path: P, format: RecordFormat) -> io::Result<()> {
+        let text = self.help_text(RenderContext::default());
+        let plain = strip_escape_sequences(&text);
+        let width = plain.lines().map(|line| line.width()).max().unwrap_or(0);
+        let height = plain.lines().count();
+        let content = match format {
+            RecordFormat::Asciicast => {
+                let title = self
+                    .cmd
+                    .get_bin_name()
+                    .unwrap_or_else(|| self.cmd.get_name());
+                crate::record::asciicast(&text, width, height, title)
             }
+        };
+        fs::write(path, content)
+    }
+
+    /// Print the help like [Self::print_help], but reuse the text
+    /// rendered by a previous call when the command, the available
+    /// width, the expanded templates, and the settings of the layout
+    /// (skin, wrapping, etc.) are the same.
+    ///
+    /// The rendered texts are stored in `cache_dir`. On a cache hit,
+    /// neither the expander nor the layout of the texts are computed,
+    /// which is noticeable for commands with hundreds of arguments.
+    ///
+    /// When the cache can't be written, eg in a read-only directory,
+    /// the help is still printed, and the error of the write returned.
+    pub fn render_cached<P: AsRef<Path>>(&self, cache_dir: P) -> io::Result<()> {
+        let cache_dir = cache_dir.as_ref();
+        let path = cache_dir.join(self.cache_file_name());
+        if let Ok(help) = fs::read_to_string(&path) {
+            self.print_logo();
+            self.write(&help);
+            return Ok(());
         }
+        let help = self.help_text(RenderContext::default());
+        self.print_logo();
+        self.write(&help);
+        fs::create_dir_all(cache_dir)?;
+        fs::write(&path, &help)
+    }
 
-        expander.set("positional-args", args);
-        expander
+    /// Remove the help texts of the command stored in `cache_dir`
+    /// by [Self::render_cached]
+    pub fn invalidate<P: AsRef<Path>>(&self, cache_dir: P) -> io::Result<()> {
+        let entries = match fs::read_dir(cache_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let prefix = format!("{}-", self.cache_name());
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(())
     }
 
-    /// Give you a mut reference to the expander, so that you can overload
-    /// the variable of the expander used to fill the templates of the help,
-    /// or add new variables for your own templates
-    pub fn expander_mut(&mut self) -> &mut OwningTemplateExpander<'static> {
-        &mut self.expander
+    /// The name of the command, usable in a file name
+    fn cache_name(&self) -> String {
+        self.cmd
+            .get_bin_name()
+            .unwrap_or_else(|| self.cmd.get_name())
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect()
     }
 
-    /// Print the provided template with the printer's expander
+    /// The name of the file caching the help, made from the version
+    /// of the command, the width, and a hash of the command, of the
+    /// expanded templates, and of the settings applied after the
+    /// expansion
     ///
-    /// It's normally more convenient to change template_keys or some
-    /// templates, unless you want none of the standard templates
-    pub fn print_template(&self, template: &str) {
-        self.skin.print_owning_expander_md(&self.expander, template);
+    /// The expanded templates already hold everything the expander
+    /// computes (the sections, the env values, the overrides, etc.),
+    /// so only the settings used by the layout are listed here.
+    fn cache_file_name(&self) -> String {
+        let width = self.help_width();
+        let mut hasher = StableHasher::default();
+        format!("{:?}", self.cmd).hash(&mut hasher);
+        self.expanded_markdown(width).hash(&mut hasher);
+        self.output().is_tty().hash(&mut hasher);
+        format!("{:?}", self.displayed_skin()).hash(&mut hasher);
+        self.table_style.hash(&mut hasher);
+        self.wrap_policy.hash(&mut hasher);
+        self.direction.hash(&mut hasher);
+        self.full_width.hash(&mut hasher);
+        self.width_strategy.hash(&mut hasher);
+        self.max_height.hash(&mut hasher);
+        self.decorations.hash(&mut hasher);
+        format!("{:?}", self.usage_style).hash(&mut hasher);
+        self.badges.hash(&mut hasher);
+        self.hyperlinks.hash(&mut hasher);
+        if let Some(styler) = &self.option_styler {
+            for arg in self.cmd.get_arguments() {
+                styler(arg).hash(&mut hasher);
+            }
+        }
+        format!(
+            "{}-{}-{}-{:016x}.txt",
+            self.cache_name(),
+            self.cmd.get_version().unwrap_or_default(),
+            width,
+            hasher.finish(),
+        )
     }
 
-    /// Print all the templates, in order
-    pub fn print_help(&self) {
+    /// The templates of the sections, as given to the layout, expanded
+    /// as markdown
+    fn expanded_markdown(&self, width: usize) -> String {
+        let mut md = String::new();
+        for key in &self.template_keys {
+            let Some(template) = self.template_for(key, width) else {
+                continue;
+            };
+            let template = self.section_template(key, template, RenderContext::default());
+            let template = TextTemplate::from(template.as_ref());
+            let section_expander = self.section_expander(key);
+            let expander = section_expander.as_ref().unwrap_or_else(|| self.expander());
+            md.push_str(&text_markdown(&expander.expand(&template)));
+            md.push('\n');
+        }
+        md
+    }
+
+    /// Return the width for which the help is rendered, resolved from
+    /// the fixed width, the `COLUMNS` environment variable, the
+    /// width of the terminal, and the minimal and maximal widths
+    pub fn resolved_width(&self) -> usize {
+        self.help_width()
+    }
+
+    /// The width for which the help is rendered
+    fn help_width(&self) -> usize {
         if self.full_width {
-            self.print_help_full_width()
+            self.terminal_width()
         } else {
-            self.print_help_content_width()
+            self.available_width()
         }
     }
 
-    fn print_help_full_width(&self) {
-        for key in &self.template_keys {
-            if let Some(template) = self.templates.get(key) {
-                self.print_template(template);
+    /// Render all the templates, in order
+    fn help_text(&self, ctx: RenderContext) -> String {
+        let help = self.help_text_with(self.expander(), ctx);
+        let Some(max_height) = self.max_height else {
+            return help;
+        };
+        let height = help.lines().count();
+        if height <= max_height {
+            return help;
+        }
+        // the greatest number of options for which the help fits is
+        // searched by bisection, the help without option being kept
+        // when even this one is too high
+        let render = |limit| self.help_text_with(&self.make_expander(Some(limit), &[]), ctx);
+        let mut fitting = render(0);
+        let (mut low, mut high) = (0, self.shown_options().len());
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            let help = render(mid);
+            if help.lines().count() <= max_height {
+                low = mid;
+                fitting = help;
+            } else {
+                high = mid;
             }
         }
+        fitting
     }
 
-    fn print_help_content_width(&self) {
-        let (width, _) = termimad::terminal_size();
-        let mut width = width as usize;
+    /// The template of a section, with its heading level, and made
+    /// shorter in compact mode
+    fn section_template<'s>(
+        &self,
+        key: &str,
+        template: &'s str,
+        ctx: RenderContext,
+    ) -> Cow<'s, str> {
+        match self.leveled_template(key, template) {
+            Cow::Borrowed(template) => self.compact_template(key, template, ctx),
+            Cow::Owned(template) => {
+                Cow::Owned(self.compact_template(key, &template, ctx).into_owned())
+            }
+        }
+    }
 
-        if let Some(max_width) = self.max_width {
-            width = width.min(max_width);
+    /// The template of a section, made shorter in compact mode
+    fn compact_template<'s>(
+        &self,
+        key: &str,
+        template: &'s str,
+        ctx: RenderContext,
+    ) -> Cow<'s, str> {
+        if !self.compact {
+            return self.prepare_template(template, ctx);
+        }
+        let mut template = template.trim_matches('\n');
+        if key == "introduction" {
+            // only the first paragraph
+            if let Some(end) = template.find("\n\n") {
+                template = &template[..end];
+            }
         }
+        self.prepare_template(template, ctx)
+    }
 
-        let mut texts: Vec<FmtText> = self
+    /// Render all the templates, in order, with the given expander
+    fn help_text_with(
+        &self,
+        expander: &OwningTemplateExpander<'static>,
+        ctx: RenderContext,
+    ) -> String {
+        if self.width_strategy != WidthStrategy::TwoPass {
+            let mut help = String::new();
+            let _ = self.for_each_section(expander, ctx, |section| {
+                help.push_str(&section);
+                Ok(())
+            });
+            return help;
+        }
+        let width = self.help_width();
+        let templates: Vec<(&str, Cow<str>)> = self
             .template_keys
             .iter()
-            .filter_map(|key| self.templates.get(key))
-            .map(|&template| {
-                let template = TextTemplate::from(template);
-                let text = self.expander.expand(&template);
-                FmtText::from_text(&self.skin, text, Some(width))
+            .filter_map(|key| Some((*key, self.template_for(key, width)?)))
+            .map(|(key, template)| (key, self.section_template(key, template, ctx)))
+            .collect();
+        let section_expanders: Vec<_> = templates
+            .iter()
+            .map(|(key, _)| self.section_expander(key))
+            .collect();
+        let mut texts: Vec<FmtText> = templates
+            .iter()
+            .zip(&section_expanders)
+            .map(|((_, template), section_expander)| {
+                let expander = section_expander.as_ref().unwrap_or(expander);
+                self.render(expander, template, width)
             })
             .collect();
+        if self.full_width {
+            return templates
+                .iter()
+                .zip(&texts)
+                .map(|((key, _), text)| self.decorated(key, self.text_string(text)))
+                .collect();
+        }
 
         let content_width = texts
             .iter()
             .fold(0, |cw, text| cw.max(text.content_width()));
 
-        for text in &mut texts {
+        let mut help = String::new();
+        for ((key, _), text) in templates.iter().zip(&mut texts) {
             text.set_rendering_width(content_width);
-            println!("{}", text);
+            help.push_str(&self.decorated(key, self.text_string(text)));
+            if !self.compact {
+                help.push('\n');
+            }
+        }
+        help
+    }
+
+    /// Render the templates one at a time, giving each rendered
+    /// section to `f`, for the streaming width strategies, and stop
+    /// at the first error of `f`, eg when the pipe was closed
+    fn for_each_section(
+        &self,
+        expander: &OwningTemplateExpander<'static>,
+        ctx: RenderContext,
+        mut f: impl FnMut(String) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let width = self.help_width();
+        for key in &self.template_keys {
+            let Some(template) = self.template_for(key, width) else {
+                continue;
+            };
+            let template = self.section_template(key, template, ctx);
+            let section_expander = self.section_expander(key);
+            let expander = section_expander.as_ref().unwrap_or(expander);
+            let mut text = self.render(expander, &template, width);
+            if self.full_width {
+                f(self.decorated(key, self.text_string(&text)))?;
+                continue;
+            }
+            if self.width_strategy == WidthStrategy::Streaming {
+                text.set_rendering_width(text.content_width());
+            }
+            let mut section = self.decorated(key, self.text_string(&text));
+            if !self.compact {
+                section.push('\n');
+            }
+            f(section)?;
+        }
+        Ok(())
+    }
+
+    /// Render the help as a string which doesn't depend on the
+    /// terminal, for snapshot tests: the width is the given one, the
+    /// skin is the default one unless a skin or a preset was given,
+    /// and there's no escape sequence unless `options.ansi` is set. The
+    /// terminal isn't queried and the values of the environment
+    /// variables aren't shown.
+    ///
+    /// ```
+    /// use clap_help::TestOptions;
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # #[command(name = "area")]
+    /// # struct Args {}
+    /// let help = clap_help::Printer::new(Args::command())
+    ///     .render_for_test(60, TestOptions::default());
+    /// assert!(help.contains("area"));
+    /// assert!(!help.contains('\x1b'));
+    /// ```
+    pub fn render_for_test(mut self, width: usize, options: TestOptions) -> String {
+        self.fixed_width = Some(width);
+        self.hyperlinks = false;
+        self.env_values = false;
+        self.legacy_colors = Some(self.legacy_colors.unwrap_or(false));
+        self.reset_expander();
+        if !options.ansi {
+            self.skin = Some(MadSkin::no_style());
+            self.option_styler = None;
+            self.usage_style = None;
+            for (_, badge) in &mut self.badges {
+                badge.color = None;
+            }
+        } else if self.skin.is_none() {
+            self.skin = Some(match &self.preset {
+                Some(preset) => preset.skin_for(Detection::Disabled, true),
+                None => MadSkin::default(),
+            });
+        }
+        self.rendered_skin = OnceLock::new();
+        self.help_text(RenderContext::default())
+    }
+
+    /// Render the help of the command or of one of its subcommands,
+    /// given by name, with the settings of this printer
+    pub(crate) fn help_text_of(&self, cmd: &Command) -> String {
+        if cmd.get_name() == self.cmd.get_name() {
+            return self.help_text(RenderContext::default());
+        }
+        fn path_of(parent: &Command, name: &str) -> Option<String> {
+            parent.get_subcommands().find_map(|subcommand| {
+                if subcommand.get_name() == name {
+                    Some(name.to_string())
+                } else {
+                    path_of(subcommand, name)
+                        .map(|path| format!("{} {path}", subcommand.get_name()))
+                }
+            })
         }
+        path_of(&self.cmd, cmd.get_name())
+            .and_then(|path| self.subcommand_printer(&path))
+            .unwrap_or_else(|| Printer::new(cmd.clone()))
+            .help_text(RenderContext::default())
+    }
+
+    /// Print the help without any table: every row of a table, eg every
+    /// option, is written as a paragraph whose values are introduced by
+    /// the headers of their columns ("Short: -v. Long: --verbose.
+    /// Description: ..."), which is easier to follow with a screen
+    /// reader or a braille display.
+    pub fn print_help_linear(&self) {
+        self.write(&self.linear_help_text());
+    }
+
+    /// Print the help in the given format: styled for the terminal,
+    /// plain, as markdown, or as JSON
+    pub fn print_help_in(&self, format: HelpFormat) {
+        match format {
+            HelpFormat::Terminal => self.print_help(),
+            HelpFormat::Plain => self.write(&strip_escape_sequences(&self.help_text())),
+            HelpFormat::Markdown => self.write(&self.help_markdown()),
+            HelpFormat::Json => self.write(&format!("{}\n", command_json(&self.cmd))),
+        }
+    }
+
+    /// Print the help in the format given by an environment variable,
+    /// eg `MYTOOL_HELP_FORMAT=json`, so that wrappers, agents, or docs
+    /// generators can get it in a structured form.
+    ///
+    /// The accepted values are `plain`, `markdown` and `json`: without
+    /// a valid value, the help is printed for the terminal.
+    pub fn print_help_auto(&self, format_env: &str) {
+        let format = std::env::var(format_env)
+            .ok()
+            .and_then(|name| HelpFormat::from_name(&name))
+            .unwrap_or_default();
+        self.print_help_in(format);
+    }
+
+    /// Render all the templates, in order, as markdown
+    fn help_markdown(&self) -> String {
+        let width = self.help_width();
+        let toc = self.templates.contains_key("toc");
+        let mut md = String::new();
+        for (key, template) in self
+            .template_keys
+            .iter()
+            .filter_map(|key| Some((key, self.template_for(key, width)?)))
+        {
+            let template = match *key {
+                "toc" if template == TEMPLATE_TOC => TEMPLATE_TOC_LINKED,
+                _ => template,
+            };
+            if toc && SECTION_HEADINGS.iter().any(|(k, _)| k == key) {
+                // the target of the link of the "toc" section
+                md.push_str(&format!("<a id=\"section-{key}\"></a>\n"));
+            }
+            let template = self.leveled_template(key, template);
+            let template = self.prepare_template(&template, RenderContext::default());
+            let template = TextTemplate::from(template.as_ref());
+            let section_expander = self.section_expander(key);
+            let expander = section_expander.as_ref().unwrap_or_else(|| self.expander());
+            let expanded = text_markdown(&expander.expand(&template));
+            md.push_str(&strip_markers(&expanded));
+            md.push('\n');
+        }
+        md
+    }
+
+    /// Render all the templates, in order, with their tables linearized
+    fn linear_help_text(&self) -> String {
+        let width = self.help_width();
+        let mut help = String::new();
+        for (key, template) in self
+            .template_keys
+            .iter()
+            .filter_map(|key| Some((key, self.template_for(key, width)?)))
+        {
+            let template = self.leveled_template(key, template);
+            let template = self.prepare_template(&template, RenderContext::default());
+            let template = TextTemplate::from(template.as_ref());
+            let section_expander = self.section_expander(key);
+            let expander = section_expander.as_ref().unwrap_or_else(|| self.expander());
+            let md = linear_markdown(&expander.expand(&template));
+            let mut text = Text::from(md.as_str());
+            apply_wrap_policy(&mut text, self.displayed_skin(), width, self.wrap_policy);
+            let mut fmt_text = fmt_text(self.displayed_skin(), text, width);
+            if self.direction == Direction::RightToLeft {
+                reorder_lines(&mut fmt_text);
+            }
+            help.push_str(&self.text_string(&fmt_text));
+            help.push('\n');
+        }
+        help
     }
 
     /// Create a printer for a specific subcommand by name
@@ -424,4 +4795,420 @@ impl<'t> Printer<'t> {
         cmd.find_subcommand(subcommand_name)
             .map(|subcmd| Self::new(subcmd.clone()))
     }
+
+    /// Change the printer of a subcommand, given by its path from this
+    /// command (eg `config` or `remote add`), when it's built by
+    /// [Self::subcommand_printer], for example to give it custom
+    /// templates or sections:
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser, Subcommand};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[command(subcommand)]
+    /// #     command: Cmd,
+    /// # }
+    /// # #[derive(Subcommand)]
+    /// # enum Cmd { Config }
+    /// let printer = clap_help::Printer::new(Args::command())
+    ///     .with_subcommand_override("config", |printer| {
+    ///         printer.with_file("~/.config/my_prog/conf.toml", "Configuration file")
+    ///     });
+    /// printer.print_help_recursive();
+    /// ```
+    pub fn with_subcommand_override<F>(mut self, path: &str, f: F) -> Self
+    where
+        F: Fn(Printer<'t>) -> Printer<'t> + Send + Sync + 't,
+    {
+        let path = path.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.subcommand_overrides.push((path, Arc::new(f)));
+        self
+    }
+
+    /// Build the printer of a subcommand, given by its path from this
+    /// command (eg `remote add`), with the skin, settings and templates
+    /// of this printer, and the overrides of this subcommand applied
+    pub fn subcommand_printer(&self, path: &str) -> Option<Printer<'t>> {
+        let mut subcommand = &self.cmd;
+        let mut names = Vec::new();
+        for name in path.split_whitespace() {
+            subcommand = subcommand.find_subcommand(name)?;
+            names.push(name);
+        }
+        let path = names.join(" ");
+        let mut printer = self.derive_for(subcommand);
+        for (override_path, f) in &self.subcommand_overrides {
+            if *override_path == path {
+                printer = f(printer);
+            }
+        }
+        Some(printer)
+    }
+
+    /// Build a printer of the given subcommand with the skin, width,
+    /// settings, templates and custom sections of this printer, so
+    /// that its help keeps the customizations of the application.
+    ///
+    /// Unlike [Self::subcommand_printer], the overrides given with
+    /// [Self::with_subcommand_override] aren't applied.
+    ///
+    /// ```
+    /// # use clap::{CommandFactory, Parser, Subcommand};
+    /// # #[derive(Parser)]
+    /// # struct Args {
+    /// #     #[command(subcommand)]
+    /// #     command: Commands,
+    /// # }
+    /// # #[derive(Subcommand)]
+    /// # enum Commands { Build }
+    /// let cmd = Args::command();
+    /// let printer = clap_help::Printer::new(cmd.clone())
+    ///     .with_max_width(100)
+    ///     .without("author");
+    /// let build = cmd.find_subcommand("build").unwrap();
+    /// let build_printer = printer.derive_for(build);
+    /// ```
+    pub fn derive_for(&self, subcommand: &Command) -> Printer<'t> {
+        let mut printer = Printer::new_with_detection(subcommand.clone(), self.detection)
+            .with_help_wrap(self.wrap_policy)
+            .with_hyperlinks(self.hyperlinks)
+            .with_locale(self.locale.clone())
+            .with_text_direction(self.direction)
+            .with_accessibility(self.accessibility)
+            .with_options_alignment(&self.options_alignment)
+            .with_level(self.level);
+        printer.skin = self.skin.clone();
+        printer.preset = self.preset;
+        printer.table_style = self.table_style;
+        printer.full_width = self.full_width;
+        printer.max_width = self.max_width;
+        printer.subcommand_tree_depth = self.subcommand_tree_depth;
+        printer.max_possible_values = self.max_possible_values;
+        printer.default_formatter = self.default_formatter.clone();
+        printer.option_styler = self.option_styler.clone();
+        printer.usage_style = self.usage_style.clone();
+        printer.enabled_features = self.enabled_features.clone();
+        printer.gate_display = self.gate_display;
+        printer.platform = self.platform;
+        printer.partials = self.partials.clone();
+        printer.section_vars = self.section_vars.clone();
+        printer.target = Arc::clone(&self.target);
+        printer.fixed_width = self.fixed_width;
+        printer.min_width = self.min_width;
+        printer.value_brackets = self.value_brackets.clone();
+        printer.width_strategy = self.width_strategy;
+        printer.heading_levels = self.heading_levels.clone();
+        printer.legacy_colors = self.legacy_colors;
+        if let Some(template) = self.templates.get("possible-values") {
+            printer
+                .templates
+                .insert("possible-values", template.clone());
+        }
+        printer.template_keys = self.template_keys.clone();
+        // the sections depending on the content are kept, with the templates of this printer
+        for (key, template) in &mut printer.templates {
+            if let Some(custom) = self.templates.get(key) {
+                *template = custom.clone();
+            }
+        }
+        // while those always present are removed when they were removed here
+        for key in ["title", "author", "usage"] {
+            if !self.templates.contains_key(key) {
+                printer.templates.remove(key);
+            }
+        }
+        // and the custom sections are added
+        for (key, template) in &self.templates {
+            if !Section::from(*key).is_builtin() {
+                printer.templates.insert(key, template.clone());
+            }
+        }
+        printer.responsive_templates = self.responsive_templates.clone();
+        printer.conditions = self.conditions.clone();
+        printer
+    }
+
+    /// Print the help of the command, then the ones of its subcommands,
+    /// recursively, with their overrides
+    pub fn print_help_recursive(&self) {
+        self.print_help();
+        self.print_subcommands_help("", &self.cmd);
+    }
+
+    /// Walk the user through the required arguments and the common
+    /// options (all the options when none is marked with
+    /// [Self::mark_common]), each one with its help, asking for their
+    /// values, then print and return the assembled command line.
+    ///
+    /// Return `None` when the input isn't a terminal or is closed.
+    ///
+    /// ```no_run
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// let printer = clap_help::Printer::new(Args::command());
+    /// if let Some(command_line) = printer.wizard() {
+    ///     eprintln!("next time, run {command_line}");
+    /// }
+    /// ```
+    #[cfg(feature = "interactive")]
+    pub fn wizard(&self) -> Option<String> {
+        use {
+            crate::{target::print_md, wizard::*},
+            termimad::crossterm::tty::IsTty,
+        };
+        if !io::stdin().is_tty() {
+            return None;
+        }
+        let skin = self.displayed_skin();
+        let cmd = &self.cmd;
+        let shown: Vec<&Arg> = cmd
+            .get_arguments()
+            .filter(|a| self.shows_arg(a) && !is_generated(a))
+            .collect();
+        let has_common = shown.iter().any(|a| self.is_common(a));
+        let is_option = |a: &Arg| a.get_short().is_some() || a.get_long().is_some();
+        // the required arguments, then the popular options,
+        // then the optional positional arguments
+        let mut questions: Vec<&Arg> = shown
+            .iter()
+            .filter(|a| a.is_required_set())
+            .copied()
+            .collect();
+        questions.extend(shown.iter().filter(|a| {
+            !a.is_required_set() && is_option(a) && (!has_common || self.is_common(a))
+        }));
+        questions.extend(
+            shown
+                .iter()
+                .filter(|a| !a.is_required_set() && !is_option(a)),
+        );
+        let mut options = Vec::new();
+        let mut positionals = Vec::new();
+        for arg in questions {
+            let name = match self.flag_name(arg) {
+                Some(flag) => flag,
+                None => format!("<{}>", arg.get_id()),
+            };
+            let (help, _) = self.help_and_deprecation(arg);
+            let mut line = LineValues::new();
+            self.fill_arg_details(&mut line, arg);
+            let details: String = ["required", "possible_values", "default"]
+                .iter()
+                .filter_map(|name| line.get(name))
+                .collect();
+            print_md(
+                skin,
+                &format!("\n**{name}** : {}{details}", self.help_md(&help)),
+            );
+            if !arg.get_action().takes_values() {
+                let prompt = format!("*{}*", self.label("enable-prompt-label"));
+                if is_yes(&read_answer(skin, &prompt)?) {
+                    options.push(self.wizard_name(arg));
+                }
+                continue;
+            }
+            let possible_values: Vec<String> = arg
+                .get_possible_values()
+                .iter()
+                .map(|value| value.get_name().to_string())
+                .collect();
+            let prompt = match arg.is_required_set() {
+                true => format!("*{}:*", self.label("value-prompt-label")),
+                false => format!(
+                    "*{} ({}):*",
+                    self.label("value-prompt-label"),
+                    self.label("skip-hint-label"),
+                ),
+            };
+            let value = loop {
+                let answer = read_answer(skin, &prompt)?;
+                let valid = possible_values.is_empty() || possible_values.contains(&answer);
+                if (answer.is_empty() && !arg.is_required_set()) || (!answer.is_empty() && valid) {
+                    break answer;
+                }
+            };
+            if value.is_empty() {
+                continue;
+            }
+            if is_option(arg) {
+                options.push(format!("{} {}", self.wizard_name(arg), shell_quote(&value)));
+            } else {
+                positionals.push((arg.get_index().unwrap_or(usize::MAX), shell_quote(&value)));
+            }
+        }
+        positionals.sort_by_key(|&(index, _)| index);
+        let mut command_line = cmd
+            .get_bin_name()
+            .unwrap_or_else(|| cmd.get_name())
+            .to_string();
+        for token in options
+            .into_iter()
+            .chain(positionals.into_iter().map(|(_, v)| v))
+        {
+            command_line.push(' ');
+            command_line.push_str(&token);
+        }
+        print_md(
+            skin,
+            &format!(
+                "\n**{}** `{command_line}`",
+                self.label("command-line-label")
+            ),
+        );
+        Some(command_line)
+    }
+
+    /// The name of an option in a command line assembled by
+    /// [Self::wizard], its long name when it has one
+    #[cfg(feature = "interactive")]
+    fn wizard_name(&self, arg: &Arg) -> String {
+        match (arg.get_long(), arg.get_short()) {
+            (Some(long), _) => format!("--{long}"),
+            (None, Some(short)) => format!("-{short}"),
+            (None, None) => arg.get_id().to_string(),
+        }
+    }
+
+    /// Print, as a single document, the help of the command then the
+    /// ones of its subcommands, recursively, each one under a heading
+    /// whose level is given by its depth, eg for `mytool --help-all | less`
+    /// or for a single page manual.
+    ///
+    /// ```no_run
+    /// # use clap::{CommandFactory, Parser};
+    /// # #[derive(Parser)]
+    /// # struct Args {}
+    /// clap_help::Printer::new(Args::command()).print_help_all();
+    /// ```
+    pub fn print_help_all(&self) {
+        let _ = self.write_help(RenderContext { help_all: true });
+        self.print_subcommands_help_all("", &self.cmd, 2);
+    }
+
+    fn print_subcommands_help_all(&self, parent_path: &str, cmd: &Command, level: usize) {
+        let name = self
+            .cmd
+            .get_bin_name()
+            .unwrap_or_else(|| self.cmd.get_name());
+        for subcommand in cmd.get_subcommands() {
+            if subcommand.is_hide_set() || subcommand.get_name() == "help" {
+                continue;
+            }
+            if self.output().is_closed() {
+                return;
+            }
+            let path = format!("{parent_path}{} ", subcommand.get_name());
+            if let Some(printer) = self.subcommand_printer(&path) {
+                self.write(&self.heading(level, &format!("{name} {}", path.trim_end())));
+                printer.print_help();
+            }
+            self.print_subcommands_help_all(&path, subcommand, level + 1);
+        }
+    }
+
+    /// Render a markdown heading, eg the one of a subcommand in
+    /// [Self::print_help_all]
+    fn heading(&self, level: usize, title: &str) -> String {
+        let md = format!("{} {title}", "#".repeat(level.clamp(1, 8)));
+        let text = FmtText::from(self.displayed_skin(), &md, Some(self.help_width()));
+        format!("{}\n", self.text_string(&text))
+    }
+
+    fn print_subcommands_help(&self, parent_path: &str, cmd: &Command) {
+        for subcommand in cmd.get_subcommands() {
+            if self.output().is_closed() {
+                return;
+            }
+            if subcommand.is_hide_set() || subcommand.get_name() == "help" {
+                continue;
+            }
+            let path = format!("{parent_path}{} ", subcommand.get_name());
+            if let Some(printer) = self.subcommand_printer(&path) {
+                printer.print_help();
+            }
+            self.print_subcommands_help(&path, subcommand);
+        }
+    }
+}
+
+/// Replace the heading of a template, ie its first line made only of
+/// a bold text or starting with `#`, with a heading of the given level
+fn set_heading_level(template: &str, level: usize) -> Cow<'_, str> {
+    let heading = template.split('\n').enumerate().find_map(|(idx, line)| {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return Some((idx, line.trim_start_matches('#').trim_start()));
+        }
+        let bold = line.strip_prefix("**")?.strip_suffix("**")?;
+        (!bold.is_empty() && !bold.contains("**")).then_some((idx, bold))
+    });
+    let Some((heading_idx, text)) = heading else {
+        return Cow::Borrowed(template);
+    };
+    let lines: Vec<Cow<str>> = template
+        .split('\n')
+        .enumerate()
+        .map(|(idx, line)| {
+            if idx == heading_idx {
+                Cow::Owned(format!("{} {text}", "#".repeat(level)))
+            } else {
+                Cow::Borrowed(line)
+            }
+        })
+        .collect();
+    Cow::Owned(lines.join("\n"))
+}
+
+/// Join value names, each one between `open` and `close`, eg
+/// `<FROM> <TO>`, in a string allocated once
+fn join_names(names: &[Str], open: &str, separator: &str, close: &str) -> String {
+    let len = names
+        .iter()
+        .map(|name| open.len() + name.len() + close.len() + separator.len())
+        .sum();
+    let mut joined = String::with_capacity(len);
+    for (i, name) in names.iter().enumerate() {
+        if i > 0 {
+            joined.push_str(separator);
+        }
+        joined.push_str(open);
+        joined.push_str(name);
+        joined.push_str(close);
+    }
+    joined
+}
+
+/// A stable identifier of the argument, made from its id, for links
+/// to its row, eg `opt-output-format`
+fn arg_anchor(arg: &Arg) -> String {
+    let id: String = arg
+        .get_id()
+        .as_str()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("opt-{id}")
+}
+
+/// Tell whether the argument accepts several values, eg `[ARGS]...`
+fn takes_several_values(arg: &Arg) -> bool {
+    matches!(arg.get_action(), ArgAction::Append)
+        || arg
+            .get_num_args()
+            .is_some_and(|range| range.max_values() > 1)
+}
+
+/// The home directory of the user, from the environment
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
 }
@@ -0,0 +1,29 @@
+use {
+    crate::help_format::json_string,
+    std::time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The format of a recording of the help, see [crate::Printer::record]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RecordFormat {
+    /// An [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/)
+    /// file, playable by asciinema players, with the whole help as a
+    /// single frame
+    #[default]
+    Asciicast,
+}
+
+/// Write the rendered text, with its escape sequences, as an asciicast
+/// of a single frame, for a terminal of the given size
+pub(crate) fn asciicast(text: &str, width: usize, height: usize, title: &str) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let header = format!(
+        "{{\"version\":2,\"width\":{width},\"height\":{height},\"timestamp\":{timestamp},\"title\":{},\"env\":{{\"TERM\":\"xterm-256color\"}}}}",
+        json_string(title),
+    );
+    // the terminal is in raw mode during a recording
+    let output = text.replace("\r\n", "\n").replace('\n', "\r\n");
+    format!("{header}\n[0.0,\"o\",{}]\n", json_string(&output))
+}
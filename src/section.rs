@@ -0,0 +1,126 @@
+use std::{fmt, str::FromStr};
+
+macro_rules! sections {
+    ($($variant:ident => $key:literal,)*) => {
+        /// The key of a section of the help, as given to
+        /// [crate::Printer::with], [crate::Printer::without], etc.
+        ///
+        /// Strings are still accepted where a section is expected, so
+        /// that custom sections can be added, but using the variants
+        /// makes a typo, eg `"option"` instead of `"options"`, a compile
+        /// error instead of a silently ignored key.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Section {
+            $(
+                #[doc = concat!("The `", $key, "` section")]
+                $variant,
+            )*
+            /// A section added by the application
+            Custom(&'static str),
+        }
+
+        impl Section {
+            /// The built-in sections, in their default order
+            pub const ALL: &'static [Section] = &[$(Self::$variant,)*];
+
+            /// The key of the section in the templates map
+            pub const fn key(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $key,)*
+                    Self::Custom(key) => key,
+                }
+            }
+
+            /// Return the built-in section of the given key, if any
+            pub fn builtin(key: &str) -> Option<Self> {
+                match key {
+                    $($key => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+sections! {
+    Banner => "banner",
+    Title => "title",
+    Author => "author",
+    Summary => "summary",
+    BeforeHelp => "before-help",
+    Introduction => "introduction",
+    Toc => "toc",
+    Usage => "usage",
+    Positionals => "positionals",
+    Options => "options",
+    Subcommands => "subcommands",
+    ExternalSubcommands => "external-subcommands",
+    SubcommandTree => "subcommand-tree",
+    Changelog => "changelog",
+    Keybindings => "keybindings",
+    Environment => "environment",
+    Files => "files",
+    Glossary => "glossary",
+    Tips => "tips",
+    PossibleValues => "possible-values",
+    ExitCodes => "exit-codes",
+    Links => "links",
+    AfterHelp => "after-help",
+    Bugs => "bugs",
+}
+
+impl Section {
+    /// Tell whether the section is one of the built-in ones
+    pub fn is_builtin(self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
+}
+
+/// Map the key to the built-in section, or to a custom one,
+/// so that keys given as strings keep working
+impl From<&'static str> for Section {
+    fn from(key: &'static str) -> Self {
+        Self::builtin(key).unwrap_or(Self::Custom(key))
+    }
+}
+
+impl AsRef<str> for Section {
+    fn as_ref(&self) -> &str {
+        self.key()
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// Error returned when parsing a key which isn't the one
+/// of a built-in section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSectionError(pub String);
+
+impl fmt::Display for ParseSectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no built-in section {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseSectionError {}
+
+/// Parse the key of a built-in section, eg read from a configuration
+/// file, failing on unknown keys:
+///
+/// ```
+/// use clap_help::Section;
+///
+/// assert_eq!("options".parse(), Ok(Section::Options));
+/// assert!("option".parse::<Section>().is_err());
+/// ```
+impl FromStr for Section {
+    type Err = ParseSectionError;
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        Self::builtin(key).ok_or_else(|| ParseSectionError(key.to_string()))
+    }
+}
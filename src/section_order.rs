@@ -0,0 +1,25 @@
+use std::fmt;
+
+/// Error returned when setting the section order, or inserting a
+/// section relative to another one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionOrderError {
+    /// The section relative to which the insertion was requested
+    /// isn't in the section order
+    UnknownSection(&'static str),
+    /// The section is given twice in the section order
+    DuplicateSection(&'static str),
+}
+
+impl fmt::Display for SectionOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownSection(key) => write!(f, "no section {key:?} in the section order"),
+            Self::DuplicateSection(key) => {
+                write!(f, "section {key:?} given twice in the section order")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SectionOrderError {}
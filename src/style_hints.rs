@@ -0,0 +1,102 @@
+use {crate::template_check::is_valid_name_char, std::borrow::Cow};
+
+/// The style hints usable in templates, as `${name|hint}` or
+/// `${name|bold|italic}`, with the markdown marks they put around
+/// the placeholder.
+///
+/// They style the plain values, like `${short}` or `${value}`, while
+/// the markdown ones, like `${help}`, keep their own styles.
+pub static STYLE_HINTS: &[(&str, &str)] = &[
+    ("bold", "**"),
+    ("italic", "*"),
+    ("code", "`"),
+    ("strikeout", "~~"),
+];
+
+/// A `${name|hint|hint}` placeholder found in a template line
+pub(crate) struct HintedPlaceholder<'s> {
+    start: usize,
+    end: usize,
+    pub name: &'s str,
+    pub hints: Vec<&'s str>,
+}
+
+/// Return the `${name|hint}` placeholders of a line
+pub(crate) fn hinted_placeholders(line: &str) -> Vec<HintedPlaceholder<'_>> {
+    let mut placeholders = Vec::new();
+    let mut idx = 0;
+    while let Some(start) = line[idx..].find("${").map(|s| s + idx) {
+        let after = &line[start + 2..];
+        let name_len = after
+            .find(|c| !is_valid_name_char(c))
+            .unwrap_or(after.len());
+        idx = start + 2 + name_len;
+        if name_len == 0 || !after[name_len..].starts_with('|') {
+            continue;
+        }
+        let Some(len) = after[name_len..].find('}') else {
+            break;
+        };
+        let hints = &after[name_len + 1..name_len + len];
+        placeholders.push(HintedPlaceholder {
+            start,
+            end: start + 2 + name_len + len + 1,
+            name: &after[..name_len],
+            hints: hints.split('|').map(str::trim).collect(),
+        });
+        idx = start + 2 + name_len + len + 1;
+    }
+    placeholders
+}
+
+/// Return the markdown mark of a style hint, eg `**` for `bold`
+pub(crate) fn hint_mark(hint: &str) -> Option<&'static str> {
+    STYLE_HINTS
+        .iter()
+        .find(|(name, _)| *name == hint)
+        .map(|(_, mark)| *mark)
+}
+
+/// Replace the `${name|hint}` placeholders of the template with
+/// `${name}` between the markdown marks of the hints, eg
+/// `**${name}**` for `${name|bold}`.
+///
+/// Unknown hints are ignored.
+pub(crate) fn apply_style_hints(template: &str) -> Cow<'_, str> {
+    if !template.contains('|') {
+        return Cow::Borrowed(template);
+    }
+    let mut styled = String::with_capacity(template.len());
+    let mut changed = false;
+    for (idx, line) in template.split('\n').enumerate() {
+        if idx > 0 {
+            styled.push('\n');
+        }
+        let mut rest_start = 0;
+        for placeholder in hinted_placeholders(line) {
+            changed = true;
+            let marks: Vec<&str> = placeholder
+                .hints
+                .iter()
+                .filter_map(|hint| hint_mark(hint))
+                .collect();
+            styled.push_str(&line[rest_start..placeholder.start]);
+            for mark in &marks {
+                styled.push_str(mark);
+            }
+            styled.push_str("${");
+            styled.push_str(placeholder.name);
+            styled.push('}');
+            for mark in marks.iter().rev() {
+                styled.push_str(mark);
+            }
+            rest_start = placeholder.end;
+        }
+        styled.push_str(&line[rest_start..]);
+    }
+    if changed {
+        Cow::Owned(styled)
+    } else {
+        Cow::Borrowed(template)
+    }
+}
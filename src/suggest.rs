@@ -0,0 +1,90 @@
+//! Suggestions for mistyped subcommands, rendered with the skin
+//! of the help.
+
+use {
+    crate::Locale,
+    clap::Command,
+    std::io,
+    termimad::{
+        minimad::{OwningTemplateExpander, TextTemplate},
+        MadSkin,
+    },
+};
+
+/// Template of the block printed by [print_unknown_subcommand]
+pub static TEMPLATE_UNKNOWN_SUBCOMMAND: &str = "
+**${error-label}** ${unknown-subcommand-label} `${given}`
+
+*${did-you-mean-label}*
+${suggestion-lines
+* **${name}** : ${help}
+}
+";
+
+/// Return the names of the visible subcommands (or their aliases)
+/// close to the given one, the closest first
+pub fn similar_subcommands<'c>(cmd: &'c Command, given: &str) -> Vec<&'c str> {
+    let max_distance = (given.chars().count() / 3).max(1);
+    let mut candidates: Vec<(usize, &str)> = cmd
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set())
+        .flat_map(|subcommand| {
+            std::iter::once(subcommand.get_name()).chain(subcommand.get_visible_aliases())
+        })
+        .filter_map(|name| {
+            let distance = strsim::damerau_levenshtein(given, name);
+            if distance <= max_distance || (!given.is_empty() && name.starts_with(given)) {
+                Some((distance, name))
+            } else {
+                None
+            }
+        })
+        .collect();
+    candidates.sort_by_key(|&(distance, _)| distance);
+    candidates.dedup_by_key(|&mut (_, name)| name);
+    candidates.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Print on stderr an error about an unknown subcommand, followed by
+/// the closest subcommands, or all of them when none is close enough.
+///
+/// ```no_run
+/// # use clap::{CommandFactory, Parser};
+/// # #[derive(Parser)]
+/// # struct Args {}
+/// clap_help::suggest::print_unknown_subcommand(
+///     &Args::command(),
+///     "stauts",
+///     &clap_help::Printer::make_skin(),
+/// );
+/// ```
+pub fn print_unknown_subcommand(cmd: &Command, given: &str, skin: &MadSkin) {
+    let mut expander = OwningTemplateExpander::new();
+    expander.set_default("");
+    let locale = Locale::default();
+    for (key, text) in locale.labels() {
+        expander.set(key, text);
+    }
+    expander.set("given", given);
+    let mut names = similar_subcommands(cmd, given);
+    if names.is_empty() {
+        expander.set(
+            "did-you-mean-label",
+            locale.get("subcommands-label").unwrap_or_default(),
+        );
+        names = cmd
+            .get_subcommands()
+            .filter(|subcommand| !subcommand.is_hide_set())
+            .map(Command::get_name)
+            .collect();
+    }
+    for name in names {
+        let sub = expander.sub("suggestion-lines");
+        sub.set("name", name);
+        if let Some(about) = cmd.find_subcommand(name).and_then(Command::get_about) {
+            sub.set_md("help", about.to_string());
+        }
+    }
+    let template = TextTemplate::from(TEMPLATE_UNKNOWN_SUBCOMMAND);
+    let _ = skin.write_owning_expander(&mut io::stderr(), &expander, &template);
+}
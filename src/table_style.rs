@@ -0,0 +1,114 @@
+use termimad::{
+    minimad::{Line, Text},
+    MadSkin, TableBorderChars, ASCII_TABLE_BORDER_CHARS, ROUNDED_TABLE_BORDER_CHARS,
+    STANDARD_TABLE_BORDER_CHARS,
+};
+
+/// Border chars drawing nothing, for borderless tables
+static NO_TABLE_BORDER_CHARS: &TableBorderChars = &TableBorderChars {
+    horizontal: ' ',
+    vertical: ' ',
+    top_left_corner: ' ',
+    top_right_corner: ' ',
+    bottom_right_corner: ' ',
+    bottom_left_corner: ' ',
+    top_junction: ' ',
+    right_junction: ' ',
+    bottom_junction: ' ',
+    left_junction: ' ',
+    cross: ' ',
+};
+
+/// The chars drawing the borders of the tables
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TableBorders {
+    /// Square corners, the default of termimad
+    #[default]
+    Standard,
+    Rounded,
+    /// Only ASCII chars, eg for CI logs
+    Ascii,
+    /// No visible border
+    None,
+}
+
+/// The style applied to the first row of the tables,
+/// when it's separated from the other rows by a rule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TableHeaderStyle {
+    /// As written in the template
+    #[default]
+    Plain,
+    Bold,
+    Italic,
+}
+
+/// How the tables of the help are drawn, see [crate::Printer::with_table_style]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TableStyle {
+    pub borders: TableBorders,
+    /// Whether to draw a rule between all rows
+    pub row_separators: bool,
+    pub header_style: TableHeaderStyle,
+}
+
+impl TableStyle {
+    /// Set the table border chars of the skin
+    pub(crate) fn apply_to_skin(&self, skin: &mut MadSkin) {
+        skin.table_border_chars = match self.borders {
+            TableBorders::Standard => STANDARD_TABLE_BORDER_CHARS,
+            TableBorders::Rounded => ROUNDED_TABLE_BORDER_CHARS,
+            TableBorders::Ascii => ASCII_TABLE_BORDER_CHARS,
+            TableBorders::None => NO_TABLE_BORDER_CHARS,
+        };
+    }
+
+    /// Style the headers of the tables of the text, and add the
+    /// rules between rows
+    pub(crate) fn apply_to_text(&self, text: &mut Text<'_>) {
+        if self.header_style == TableHeaderStyle::Plain && !self.row_separators {
+            return;
+        }
+        let lines = std::mem::take(&mut text.lines);
+        let mut rule = None; // the last rule of the current table
+        for idx in 0..lines.len() {
+            let mut line = lines[idx].clone();
+            let next = lines.get(idx + 1);
+            match &mut line {
+                Line::TableRule(table_rule) => {
+                    rule = Some(table_rule.clone());
+                }
+                Line::TableRow(row) => {
+                    let first_row = !matches!(
+                        idx.checked_sub(1).map(|i| &lines[i]),
+                        Some(Line::TableRow(_))
+                    );
+                    // a header row is separated from the other rows by a rule
+                    let is_header = first_row
+                        && matches!(next, Some(Line::TableRule(_)))
+                        && matches!(lines.get(idx + 2), Some(Line::TableRow(_)));
+                    if is_header {
+                        for compound in row.cells.iter_mut().flat_map(|c| &mut c.compounds) {
+                            match self.header_style {
+                                TableHeaderStyle::Plain => {}
+                                TableHeaderStyle::Bold => compound.bold = true,
+                                TableHeaderStyle::Italic => compound.italic = true,
+                            }
+                        }
+                    }
+                    if self.row_separators && matches!(next, Some(Line::TableRow(_))) {
+                        text.lines.push(line);
+                        if let Some(rule) = &rule {
+                            text.lines.push(Line::TableRule(rule.clone()));
+                        }
+                        continue;
+                    }
+                }
+                _ => {
+                    rule = None;
+                }
+            }
+            text.lines.push(line);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use {
+    std::{
+        fmt,
+        io::{self, Write},
+    },
+    termimad::{crossterm::tty::IsTty, MadSkin},
+};
+
+/// Where the help is written, see [crate::Printer::with_target]
+#[derive(Default)]
+pub enum Target {
+    #[default]
+    Stdout,
+    Stderr,
+    /// Any writer, eg a buffer of an embedded shell, which is
+    /// never considered to be a terminal
+    Writer(Box<dyn Write + Send>),
+}
+
+impl Target {
+    /// Tell whether the target is a terminal
+    pub fn is_tty(&self) -> bool {
+        match self {
+            Self::Stdout => io::stdout().is_tty(),
+            Self::Stderr => io::stderr().is_tty(),
+            Self::Writer(_) => false,
+        }
+    }
+}
+
+impl Write for Target {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Stdout => io::stdout().write(buf),
+            Self::Stderr => io::stderr().write(buf),
+            Self::Writer(w) => w.write(buf),
+        }
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stdout => io::stdout().flush(),
+            Self::Stderr => io::stderr().flush(),
+            Self::Writer(w) => w.flush(),
+        }
+    }
+}
+
+impl fmt::Debug for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdout => write!(f, "Stdout"),
+            Self::Stderr => write!(f, "Stderr"),
+            Self::Writer(_) => write!(f, "Writer"),
+        }
+    }
+}
+
+/// The target of a printer, remembering when the reader went away,
+/// eg in `mytool --help | head -1`, so that the rest of the help
+/// isn't rendered for nothing
+#[derive(Debug, Default)]
+pub(crate) struct Output {
+    target: Target,
+    closed: bool,
+}
+
+impl Output {
+    pub fn new(target: Target) -> Self {
+        Self {
+            target,
+            closed: false,
+        }
+    }
+    pub fn is_tty(&self) -> bool {
+        self.target.is_tty()
+    }
+    /// Tell whether a write failed because the pipe was closed
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+    fn check(&mut self, e: io::Error) -> io::Error {
+        if e.kind() == io::ErrorKind::BrokenPipe {
+            self.closed = true;
+        }
+        e
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.closed {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        self.target.write(buf).map_err(|e| self.check(e))
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        if self.closed {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        self.target.flush().map_err(|e| self.check(e))
+    }
+}
+
+/// Print a markdown text on stdout, ignoring the errors, eg when
+/// the pipe was closed, instead of panicking like `print!`
+pub(crate) fn print_md(skin: &MadSkin, md: &str) {
+    let _ = write!(io::stdout(), "{}", skin.term_text(md));
+}
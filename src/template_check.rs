@@ -0,0 +1,220 @@
+use {
+    crate::{
+        conditions::condition_opening,
+        partials::partial_references,
+        style_hints::{hint_mark, hinted_placeholders},
+        *,
+    },
+    std::fmt,
+};
+
+/// A problem found in a template by [Printer::check_templates]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateIssue {
+    /// The key of the template, eg "options"
+    pub key: &'static str,
+    /// Index of the line in the template
+    pub line: usize,
+    pub kind: TemplateIssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateIssueKind {
+    /// A `${name}` placeholder the expander will never fill,
+    /// probably a typo. It would be rendered as an empty string.
+    UnknownVariable {
+        name: String,
+        sub_loop: Option<String>,
+    },
+    /// A `${name` sub-loop opening for a sub-loop the expander
+    /// doesn't fill: this part of the template would be skipped.
+    UnknownSubLoop { name: String },
+    /// A `${>name}` reference to a partial which isn't defined
+    UnknownPartial { name: String },
+    /// A `${?name` conditional block testing a condition which is
+    /// neither a built-in one nor set with [Printer::with_condition]:
+    /// the block would never be rendered.
+    UnknownCondition { name: String },
+    /// A `${name|hint}` style hint which isn't one of [STYLE_HINTS]:
+    /// the value would be rendered without this style.
+    UnknownStyleHint { name: String, hint: String },
+    /// A custom section in the section order which has no template,
+    /// eg because it was misspelled: nothing would be printed for it.
+    MissingTemplate,
+}
+
+impl TemplateIssue {
+    /// Tell whether the issue breaks the rendering whatever the
+    /// variables set with [Printer::expander_mut], eg a reference
+    /// to an unknown partial
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self.kind,
+            TemplateIssueKind::UnknownPartial { .. }
+                | TemplateIssueKind::UnknownCondition { .. }
+                | TemplateIssueKind::UnknownStyleHint { .. }
+        )
+    }
+}
+
+impl fmt::Display for TemplateIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "template {:?}, line {}: ", self.key, self.line)?;
+        match &self.kind {
+            TemplateIssueKind::UnknownVariable {
+                name,
+                sub_loop: Some(sub_loop),
+            } => write!(f, "unknown variable ${{{name}}} in sub-loop {sub_loop:?}"),
+            TemplateIssueKind::UnknownVariable {
+                name,
+                sub_loop: None,
+            } => write!(f, "unknown variable ${{{name}}}"),
+            TemplateIssueKind::UnknownSubLoop { name } => write!(f, "unknown sub-loop {name:?}"),
+            TemplateIssueKind::UnknownPartial { name } => write!(f, "unknown partial {name:?}"),
+            TemplateIssueKind::UnknownCondition { name } => {
+                write!(f, "unknown condition {name:?}")
+            }
+            TemplateIssueKind::UnknownStyleHint { name, hint } => {
+                write!(f, "unknown style hint {hint:?} of ${{{name}}}")
+            }
+            TemplateIssueKind::MissingTemplate => {
+                write!(f, "missing, while the section is in the section order")
+            }
+        }
+    }
+}
+
+/// Same rule than minimad for the names of placeholders
+pub(crate) fn is_valid_name_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-'
+}
+
+/// If the line opens a sub-loop (eg `${option-lines`), return its name
+pub(crate) fn sub_loop_opening(line: &str) -> Option<&str> {
+    let name = line.strip_prefix("${")?;
+    if !name.is_empty() && name.chars().all(is_valid_name_char) {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Return the names of the `${name}` placeholders of a line
+pub(crate) fn placeholders(line: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("${") {
+        rest = &rest[start + 2..];
+        let end = rest.find(|c| !is_valid_name_char(c)).unwrap_or(rest.len());
+        if end > 0 && rest[end..].starts_with('}') {
+            names.push(&rest[..end]);
+            rest = &rest[end + 1..];
+        }
+    }
+    names
+}
+
+/// Check a template, whose partials are expanded, against the known
+/// variables and conditions
+pub(crate) fn check_template(
+    key: &'static str,
+    template: &str,
+    variables: &[VariableInfo],
+    conditions: &[&str],
+    issues: &mut Vec<TemplateIssue>,
+) {
+    let is_known = |name: &str, scope: VariableScope| {
+        variables.iter().any(|v| {
+            v.name == name
+                && !v.is_sub_loop()
+                && (v.scope == scope || v.scope == VariableScope::Global)
+        })
+    };
+    // the current sub-loop, and whether it's a known one
+    let mut sub_loop: Option<(&str, Option<VariableScope>)> = None;
+    // the open conditional blocks, with whether they're in the sub-loop
+    let mut conditionals: Vec<bool> = Vec::new();
+    for (idx, line) in template.lines().enumerate() {
+        for name in partial_references(line) {
+            issues.push(TemplateIssue {
+                key,
+                line: idx,
+                kind: TemplateIssueKind::UnknownPartial {
+                    name: name.to_string(),
+                },
+            });
+        }
+        if let Some((name, _)) = condition_opening(line) {
+            if !conditions.contains(&name) {
+                issues.push(TemplateIssue {
+                    key,
+                    line: idx,
+                    kind: TemplateIssueKind::UnknownCondition {
+                        name: name.to_string(),
+                    },
+                });
+            }
+            conditionals.push(sub_loop.is_some());
+            continue;
+        }
+        if line == "}" && conditionals.last() == Some(&sub_loop.is_some()) {
+            conditionals.pop();
+            continue;
+        }
+        if let Some(name) = sub_loop_opening(line) {
+            let known = variables
+                .iter()
+                .find(|v| v.is_sub_loop() && v.name == name)
+                .map(|v| VariableScope::SubLoop(v.name));
+            if known.is_none() {
+                issues.push(TemplateIssue {
+                    key,
+                    line: idx,
+                    kind: TemplateIssueKind::UnknownSubLoop {
+                        name: name.to_string(),
+                    },
+                });
+            }
+            sub_loop = Some((name, known));
+            continue;
+        }
+        if line == "}" && sub_loop.is_some() {
+            sub_loop = None;
+            continue;
+        }
+        let scope = match sub_loop {
+            Some((_, None)) => {
+                continue; // already reported
+            }
+            Some((_, Some(scope))) => scope,
+            None => VariableScope::Global,
+        };
+        for placeholder in hinted_placeholders(line) {
+            for hint in &placeholder.hints {
+                if hint_mark(hint).is_none() {
+                    issues.push(TemplateIssue {
+                        key,
+                        line: idx,
+                        kind: TemplateIssueKind::UnknownStyleHint {
+                            name: placeholder.name.to_string(),
+                            hint: hint.to_string(),
+                        },
+                    });
+                }
+            }
+        }
+        let hinted = hinted_placeholders(line).into_iter().map(|p| p.name);
+        for name in placeholders(line).into_iter().chain(hinted) {
+            if !is_known(name, scope) {
+                issues.push(TemplateIssue {
+                    key,
+                    line: idx,
+                    kind: TemplateIssueKind::UnknownVariable {
+                        name: name.to_string(),
+                        sub_loop: sub_loop.map(|(name, _)| name.to_string()),
+                    },
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+/// Options of [crate::Printer::render_for_test]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TestOptions {
+    /// Keep the escape sequences of the styles. They're those of the
+    /// skin given to the printer, or of the default skin when none was
+    /// given, as the automatically selected one depends on the terminal
+    pub ansi: bool,
+}
@@ -0,0 +1,29 @@
+use std::fmt;
+
+/// Error returned when an argument, or a subcommand, given by name
+/// to a setting of the printer isn't in the command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownArg {
+    /// The name which was given, eg `--format`
+    pub name: String,
+}
+
+impl UnknownArg {
+    pub(crate) fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for UnknownArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no argument or subcommand {:?} in the command",
+            self.name
+        )
+    }
+}
+
+impl std::error::Error for UnknownArg {}
@@ -0,0 +1,105 @@
+use {
+    crate::line_style::{index_marker, restyle_marked, USAGE_MARK},
+    termimad::{
+        crossterm::style::{Attribute, Color},
+        CompoundStyle, MadSkin,
+    },
+};
+
+/// Styles of the tokens of the usage line, applied when the usage
+/// template uses `${usage-styled}`, eg [crate::TEMPLATE_USAGE_STYLED],
+/// see [crate::Printer::usage_style_mut]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageStyle {
+    /// Style of the name of the program
+    pub program: CompoundStyle,
+    /// Style of the flags, eg `--`, and of the `options` placeholder
+    pub flag: CompoundStyle,
+    /// Style of the names of the values, eg `ROOT`
+    pub metavar: CompoundStyle,
+    /// Style of the brackets around the optional and the value parts
+    pub bracket: CompoundStyle,
+}
+
+impl UsageStyle {
+    /// Build a usage style matching the skin: the name of the program
+    /// is bold, the flags are styled as inline code, and the names of
+    /// the values are italic
+    pub fn from_skin(skin: &MadSkin) -> Self {
+        Self {
+            program: skin.bold.clone(),
+            flag: skin.inline_code.clone(),
+            metavar: skin.italic.clone(),
+            bracket: CompoundStyle::new(Some(Color::AnsiValue(244)), None, Attribute::Dim.into()),
+        }
+    }
+    fn style(&self, token: UsageToken) -> &CompoundStyle {
+        match token {
+            UsageToken::Program => &self.program,
+            UsageToken::Flag => &self.flag,
+            UsageToken::Metavar => &self.metavar,
+            UsageToken::Bracket => &self.bracket,
+        }
+    }
+}
+
+/// The kind of a part of the usage line, each one having its style
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UsageToken {
+    Program,
+    Flag,
+    Metavar,
+    Bracket,
+}
+
+const TOKENS: &[UsageToken] = &[
+    UsageToken::Program,
+    UsageToken::Flag,
+    UsageToken::Metavar,
+    UsageToken::Bracket,
+];
+
+/// A usage line made of styled tokens
+#[derive(Debug, Default)]
+pub(crate) struct UsageLine {
+    parts: Vec<(Option<UsageToken>, String)>,
+}
+
+impl UsageLine {
+    pub fn push(&mut self, token: UsageToken, text: &str) {
+        self.parts.push((Some(token), text.to_string()));
+    }
+    /// Add a part which isn't styled, eg a space
+    pub fn push_plain(&mut self, text: &str) {
+        self.parts.push((None, text.to_string()));
+    }
+    /// Return the line, without style
+    pub fn plain(&self) -> String {
+        self.parts.iter().map(|(_, text)| text.as_str()).collect()
+    }
+    /// Return the line, with the tokens surrounded with invisible
+    /// markers, to be styled by [style_usage]
+    pub fn marked(&self) -> String {
+        let mut marked = String::new();
+        for (token, text) in &self.parts {
+            match token.and_then(|token| TOKENS.iter().position(|&t| t == token)) {
+                Some(idx) if !text.is_empty() => {
+                    marked.push_str(&index_marker(USAGE_MARK, idx));
+                    marked.push_str(text);
+                    marked.push(USAGE_MARK);
+                }
+                _ => marked.push_str(text),
+            }
+        }
+        marked
+    }
+}
+
+/// Style the tokens of the usage found with their markers
+/// in the rendered text
+pub(crate) fn style_usage(rendered: &str, style: &UsageStyle) -> String {
+    restyle_marked(rendered, USAGE_MARK, |idx, text| {
+        let token = TOKENS.get(idx)?;
+        Some(style.style(*token).apply_to(text).to_string())
+    })
+}
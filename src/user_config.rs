@@ -0,0 +1,174 @@
+use {
+    crate::*,
+    std::{fs, path::PathBuf},
+};
+
+/// The settings the user may give in their configuration file,
+/// see [Printer::with_user_config]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct UserConfig {
+    pub theme: Option<String>,
+    pub max_width: Option<usize>,
+    pub options_layout: Option<String>,
+    pub pager: Option<bool>,
+    /// The entries which were ignored, described for the user
+    pub issues: Vec<String>,
+}
+
+impl UserConfig {
+    /// Parse the flat `key = value` lines of the configuration, ie the
+    /// subset of TOML it needs. Comments and tables are ignored, and
+    /// so are the unknown or invalid entries, which are reported in
+    /// `issues`.
+    pub fn parse(content: &str) -> Self {
+        let mut config = Self::default();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+                continue;
+            }
+            let mut issue = |issue: String| {
+                config.issues.push(format!("line {}: {issue}", idx + 1));
+            };
+            let Some((key, value)) = line.split_once('=') else {
+                issue(format!("expected `key = value`, found {line:?}"));
+                continue;
+            };
+            let key = key.trim();
+            let value = value.trim();
+            let value = match value.find(" #") {
+                Some(idx) if !value.starts_with(['"', '\'']) => value[..idx].trim_end(),
+                _ => value,
+            };
+            let string = || {
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.split_once('"'))
+                    .or_else(|| value.strip_prefix('\'').and_then(|v| v.split_once('\'')))?
+                    .0;
+                Some(value.to_string())
+            };
+            match key {
+                "theme" => match string() {
+                    Some(name) if StylePreset::find(&name).is_some() => config.theme = Some(name),
+                    _ => issue(format!("{value} isn't the name of a style preset")),
+                },
+                "max_width" => match value.parse() {
+                    Ok(max_width) => config.max_width = Some(max_width),
+                    Err(_) => issue(format!("{value} isn't a valid max_width")),
+                },
+                "options_layout" => match string() {
+                    Some(name) if options_layout(&name).is_some() => {
+                        config.options_layout = Some(name);
+                    }
+                    _ => issue(format!("{value} isn't an options layout")),
+                },
+                "pager" => match value.parse() {
+                    Ok(pager) => config.pager = Some(pager),
+                    Err(_) => issue(format!(
+                        "{value} isn't a valid pager, expected true or false"
+                    )),
+                },
+                _ => issue(format!("unknown key {key:?}")),
+            }
+        }
+        config
+    }
+}
+
+/// The path of the configuration file of the user, ie
+/// `$XDG_CONFIG_HOME/clap-help/config.toml`, the configuration
+/// directory defaulting to `~/.config`
+pub(crate) fn user_config_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .filter(|home| !home.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(config_dir.join("clap-help").join("config.toml"))
+}
+
+/// Read the configuration file of the user, if any
+pub(crate) fn read_user_config() -> Option<UserConfig> {
+    let content = fs::read_to_string(user_config_path()?).ok()?;
+    Some(UserConfig::parse(&content))
+}
+
+/// The names of the layouts of the options section, accepted by
+/// [options_layout]
+pub(crate) const OPTIONS_LAYOUTS: &[&str] = &[
+    "table",
+    "merged",
+    "list",
+    "verbose",
+    "two-columns",
+    "three-columns",
+];
+
+/// Return the options template of the layout of the given name, one
+/// of `table`, `merged`, `list`, `verbose`, `two-columns` and
+/// `three-columns`, as accepted in the configuration file of the user
+/// (see [Printer::with_user_config])
+pub fn options_layout(name: &str) -> Option<&'static str> {
+    match name {
+        "table" => Some(TEMPLATE_OPTIONS),
+        "merged" => Some(TEMPLATE_OPTIONS_MERGED_VALUE),
+        "list" => Some(TEMPLATE_OPTIONS_LIST),
+        "verbose" => Some(TEMPLATE_OPTIONS_VERBOSE),
+        "two-columns" => Some(TEMPLATE_OPTIONS_TWO_COLUMN),
+        "three-columns" => Some(TEMPLATE_OPTIONS_THREE_COLUMN),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_entries() {
+        let config = UserConfig::parse(
+            r#"
+# my preferences
+[display]
+theme = "dark"
+max_width = 100 # columns
+options_layout = 'two-columns'
+pager = true
+"#,
+        );
+        assert_eq!(
+            config,
+            UserConfig {
+                theme: Some("dark".to_string()),
+                max_width: Some(100),
+                options_layout: Some("two-columns".to_string()),
+                pager: Some(true),
+                issues: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_reports_ignored_entries() {
+        let config = UserConfig::parse(
+            "theme = \"nope\"\nmax_width = wide\nlayout = \"table\"\npager = yes\ngarbage\n",
+        );
+        assert_eq!(config.theme, None);
+        assert_eq!(config.max_width, None);
+        assert_eq!(config.pager, None);
+        assert_eq!(
+            config.issues,
+            vec![
+                "line 1: \"nope\" isn't the name of a style preset",
+                "line 2: wide isn't a valid max_width",
+                "line 3: unknown key \"layout\"",
+                "line 4: yes isn't a valid pager, expected true or false",
+                "line 5: expected `key = value`, found \"garbage\"",
+            ]
+        );
+    }
+}
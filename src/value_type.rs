@@ -0,0 +1,179 @@
+use {
+    clap::{Arg, Command, ValueHint},
+    std::{
+        any::TypeId,
+        ffi::OsString,
+        ops::{Bound, RangeBounds},
+        path::PathBuf,
+        time::Duration,
+    },
+};
+
+/// The kind of value an option takes, found from its value hint
+/// or from the type of its value parser
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    Path,
+    Url,
+    /// A number, with the name of its type, eg `u16`
+    Number(&'static str),
+    Duration,
+    Bool,
+    Text,
+}
+
+impl ValueType {
+    /// Return the type of the value of the argument, when known
+    pub fn of(arg: &Arg) -> Option<Self> {
+        match arg.get_value_hint() {
+            ValueHint::AnyPath
+            | ValueHint::FilePath
+            | ValueHint::DirPath
+            | ValueHint::ExecutablePath => return Some(Self::Path),
+            ValueHint::Url => return Some(Self::Url),
+            _ => {}
+        }
+        let type_id = arg.get_value_parser().type_id();
+        macro_rules! numbers {
+            ($($t:ty),*) => {
+                $(
+                    if type_id == TypeId::of::<$t>() {
+                        return Some(Self::Number(stringify!($t)));
+                    }
+                )*
+            };
+        }
+        numbers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+        if type_id == TypeId::of::<PathBuf>() {
+            Some(Self::Path)
+        } else if type_id == TypeId::of::<Duration>() {
+            Some(Self::Duration)
+        } else if type_id == TypeId::of::<bool>() {
+            Some(Self::Bool)
+        } else if type_id == TypeId::of::<String>() || type_id == TypeId::of::<OsString>() {
+            Some(Self::Text)
+        } else {
+            None
+        }
+    }
+
+    /// The name of the type, eg `u16` or `path`, as `${value-type}`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Url => "url",
+            Self::Number(name) => name,
+            Self::Duration => "duration",
+            Self::Bool => "bool",
+            Self::Text => "string",
+        }
+    }
+
+    /// The markdown of the value placeholder, as `${value-styled}`:
+    /// paths and urls are in italic, numbers and durations in bold
+    pub(crate) fn styled(self, value: &str) -> String {
+        match self {
+            Self::Path | Self::Url => format!("*{value}*"),
+            Self::Number(_) | Self::Duration => format!("**{value}**"),
+            Self::Bool | Self::Text => value.to_string(),
+        }
+    }
+
+    /// The smallest and greatest values of an integer type
+    fn integer_bounds(self) -> Option<(i128, i128)> {
+        let Self::Number(name) = self else {
+            return None;
+        };
+        Some(match name {
+            "u8" => (0, u8::MAX.into()),
+            "u16" => (0, u16::MAX.into()),
+            "u32" => (0, u32::MAX.into()),
+            "u64" | "usize" => (0, u64::MAX.into()),
+            "i8" => (i8::MIN.into(), i8::MAX.into()),
+            "i16" => (i16::MIN.into(), i16::MAX.into()),
+            "i32" => (i32::MIN.into(), i32::MAX.into()),
+            "i64" | "isize" => (i64::MIN.into(), i64::MAX.into()),
+            _ => return None,
+        })
+    }
+}
+
+/// Return the range of the values accepted by the parser of an integer
+/// argument, eg `1..=65535`, when it's narrower than the range of its type.
+///
+/// Only the types clap parses with its ranged integer parsers, eg with
+/// `value_parser!(u16).range(1..)`, are considered. As clap doesn't
+/// expose the range of a parser, it's found by submitting values to it.
+pub(crate) fn value_range(arg: &Arg) -> Option<String> {
+    let (min, max) = ValueType::of(arg)?.integer_bounds()?;
+    let mut probe = Command::new("probe").no_binary_name(true).arg(
+        Arg::new("value")
+            .value_parser(arg.get_value_parser().clone())
+            .allow_negative_numbers(true),
+    );
+    let mut accepts = |n: i128| probe.try_get_matches_from_mut([n.to_string()]).is_ok();
+    let lower = !accepts(min);
+    let upper = !accepts(max);
+    if !lower && !upper {
+        return None;
+    }
+    // an accepted value is needed to search the bounds around it
+    let powers = (0..20).map(|exp| 10i128.pow(exp));
+    let valid = [0, min, max]
+        .into_iter()
+        .chain(powers.clone())
+        .chain(powers.map(|p| -p))
+        .filter(|&n| min <= n && n <= max)
+        .find(|&n| accepts(n))?;
+    // smallest accepted value, searched in ]min, valid]
+    let lower = lower.then(|| {
+        let (mut refused, mut accepted) = (min, valid);
+        while accepted - refused > 1 {
+            let mid = refused + (accepted - refused) / 2;
+            if accepts(mid) {
+                accepted = mid;
+            } else {
+                refused = mid;
+            }
+        }
+        accepted
+    });
+    // greatest accepted value, searched in [valid, max[
+    let upper = upper.then(|| {
+        let (mut accepted, mut refused) = (valid, max);
+        while refused - accepted > 1 {
+            let mid = accepted + (refused - accepted) / 2;
+            if accepts(mid) {
+                accepted = mid;
+            } else {
+                refused = mid;
+            }
+        }
+        accepted
+    });
+    Some(match (lower, upper) {
+        (Some(lower), Some(upper)) => format!("{lower}..={upper}"),
+        (Some(lower), None) => format!("{lower}.."),
+        (None, Some(upper)) => format!("..={upper}"),
+        (None, None) => unreachable!(),
+    })
+}
+
+/// Format a range of values, eg `1..=65535`, or return `None`
+/// when it's unbounded
+pub(crate) fn format_range(range: &impl RangeBounds<i64>) -> Option<String> {
+    let start = match range.start_bound() {
+        Bound::Included(start) => start.to_string(),
+        Bound::Excluded(start) => start.saturating_add(1).to_string(),
+        Bound::Unbounded => String::new(),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => format!("={end}"),
+        Bound::Excluded(end) => end.to_string(),
+        Bound::Unbounded => String::new(),
+    };
+    if start.is_empty() && end.is_empty() {
+        return None;
+    }
+    Some(format!("{start}..{end}"))
+}
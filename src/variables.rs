@@ -0,0 +1,253 @@
+use std::borrow::Cow;
+
+/// Where a template variable can be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VariableScope {
+    /// Usable anywhere in a template
+    Global,
+    /// Usable only inside the named sub-loop, eg in `${option-lines ... }`
+    SubLoop(&'static str),
+}
+
+/// Whether a variable is a simple value or a sub-loop
+/// (a template part repeated for every item, eg every option)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VariableKind {
+    Value,
+    SubLoop,
+}
+
+/// Description of a variable filled by the expander of a [crate::Printer],
+/// so that you know what `${...}` keys you can use in your templates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableInfo {
+    pub name: &'static str,
+    pub scope: VariableScope,
+    pub kind: VariableKind,
+    /// A value the variable could have, for illustration
+    pub example: Cow<'static, str>,
+}
+
+impl VariableInfo {
+    pub const fn global(name: &'static str, example: &'static str) -> Self {
+        Self {
+            name,
+            scope: VariableScope::Global,
+            kind: VariableKind::Value,
+            example: Cow::Borrowed(example),
+        }
+    }
+    pub const fn sub_loop(name: &'static str) -> Self {
+        Self {
+            name,
+            scope: VariableScope::Global,
+            kind: VariableKind::SubLoop,
+            example: Cow::Borrowed(""),
+        }
+    }
+    pub const fn in_loop(
+        sub_loop: &'static str,
+        name: &'static str,
+        example: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            scope: VariableScope::SubLoop(sub_loop),
+            kind: VariableKind::Value,
+            example: Cow::Borrowed(example),
+        }
+    }
+    pub fn is_sub_loop(&self) -> bool {
+        self.kind == VariableKind::SubLoop
+    }
+}
+
+/// The sub-loops listing options, which all have the variables
+/// of `option-lines`
+pub(crate) const OPTION_SUB_LOOPS: &[&str] = &[
+    "option-lines",
+    "value-option-lines",
+    "common-option-lines",
+    "advanced-option-lines",
+];
+
+/// The variables the printer sets from the clap command
+pub static BUILTIN_VARIABLES: &[VariableInfo] = &[
+    VariableInfo::global("name", "my_prog"),
+    VariableInfo::global("author", "dystroy"),
+    VariableInfo::global("version", "1.0.2"),
+    VariableInfo::global("before_help", "Run it in a clean directory"),
+    VariableInfo::global("before_long_help", "Run it in a clean directory"),
+    VariableInfo::global("after_help", "See the website for examples"),
+    VariableInfo::global("after_long_help", "See the website for examples"),
+    VariableInfo::global("positional-args", " [ROOT]"),
+    VariableInfo::global("usage-styled", "my_prog [options] [--json | --yaml] [ROOT]"),
+    VariableInfo::global("usage-groups", " [--json | --yaml]"),
+    VariableInfo::global(
+        "external-subcommands-note",
+        "*Additional plugins:* `mytool-<name>` programs on the PATH are available as subcommands",
+    ),
+    VariableInfo::global("option-count", "14"),
+    VariableInfo::global(
+        "badge-legend",
+        "`experimental` may change in future versions",
+    ),
+    VariableInfo::global("subcommand-count", "3"),
+    VariableInfo::global("positional-count", "1"),
+    VariableInfo::global("summary", "14 options, 3 subcommands, 1 argument"),
+    VariableInfo::global("error", "invalid value for `--width`"),
+    VariableInfo::global("homepage", "https://dystroy.org/broot"),
+    VariableInfo::global("repository", "https://github.com/Canop/broot"),
+    VariableInfo::sub_loop("more-options-lines"),
+    VariableInfo::in_loop("more-options-lines", "count", "12"),
+    VariableInfo::sub_loop("before-help-lines"),
+    VariableInfo::in_loop("before-help-lines", "line", "Run it in a clean directory"),
+    VariableInfo::sub_loop("after-help-lines"),
+    VariableInfo::in_loop("after-help-lines", "line", "See the website for examples"),
+    VariableInfo::sub_loop("banner-lines"),
+    VariableInfo::in_loop("banner-lines", "line", " ### "),
+    VariableInfo::sub_loop("option-lines"),
+    VariableInfo::in_loop("option-lines", "id", "width"),
+    VariableInfo::in_loop("option-lines", "anchor", "opt-width"),
+    VariableInfo::in_loop("option-lines", "short", "-w"),
+    VariableInfo::in_loop("option-lines", "long", "--width"),
+    VariableInfo::in_loop("option-lines", "help", "Width, from there, to there"),
+    VariableInfo::in_loop("option-lines", "value", "WIDTH"),
+    VariableInfo::in_loop("option-lines", "value-names", "FROM, TO"),
+    VariableInfo::in_loop("option-lines", "value-bare", "WIDTH"),
+    VariableInfo::in_loop("option-lines", "value-type", "u16"),
+    VariableInfo::in_loop("option-lines", "value-styled", "**WIDTH**"),
+    VariableInfo::in_loop("option-lines", "value-braced", "<WIDTH>"),
+    VariableInfo::in_loop("option-lines", "value-short", "WIDTH"),
+    VariableInfo::in_loop("option-lines", "value-short-braced", "<WIDTH>"),
+    VariableInfo::in_loop("option-lines", "value-long", "WIDTH"),
+    VariableInfo::in_loop("option-lines", "value-long-braced", "<WIDTH>"),
+    VariableInfo::in_loop("option-lines", "required", " *(required)*"),
+    VariableInfo::in_loop(
+        "option-lines",
+        "possible_values",
+        " Possible values: [`fast`, `precise`]",
+    ),
+    VariableInfo::in_loop("option-lines", "repeat-hint", " *(may be repeated)*"),
+    VariableInfo::in_loop(
+        "option-lines",
+        "count-levels",
+        " *Levels:* `-v` info, `-vv` debug, `-vvv` trace",
+    ),
+    VariableInfo::in_loop("option-lines", "value-range", "1..=100"),
+    VariableInfo::in_loop("option-lines", "details-constraints", " Range: `1..=100`"),
+    VariableInfo::in_loop("option-lines", "default", " Default: `3`"),
+    VariableInfo::in_loop(
+        "option-lines",
+        "current",
+        " *Current:* `5` *(from the command line)*",
+    ),
+    VariableInfo::in_loop("option-lines", "current-value", "5"),
+    VariableInfo::in_loop(
+        "option-lines",
+        "deprecated-note",
+        " *Deprecated:* use `--width` instead",
+    ),
+    VariableInfo::in_loop(
+        "option-lines",
+        "gate-note",
+        " *(requires feature `unstable`)*",
+    ),
+    VariableInfo::sub_loop("value-option-lines"),
+    VariableInfo::sub_loop("common-option-lines"),
+    VariableInfo::sub_loop("advanced-option-lines"),
+    VariableInfo::sub_loop("possible-value-lines"),
+    VariableInfo::in_loop("possible-value-lines", "option", "--mode"),
+    VariableInfo::in_loop("possible-value-lines", "value", "`fast`"),
+    VariableInfo::in_loop("possible-value-lines", "help", "Skip the slow checks"),
+    VariableInfo::sub_loop("flag-pairs"),
+    VariableInfo::in_loop("flag-pairs", "flag-1", "-k, --kill-birds"),
+    VariableInfo::in_loop("flag-pairs", "help-1", "Kill all birds"),
+    VariableInfo::in_loop("flag-pairs", "flag-2", "-V, --version"),
+    VariableInfo::in_loop("flag-pairs", "help-2", "Print version"),
+    VariableInfo::sub_loop("flag-triplets"),
+    VariableInfo::in_loop("flag-triplets", "flag-1", "-k, --kill-birds"),
+    VariableInfo::in_loop("flag-triplets", "help-1", "Kill all birds"),
+    VariableInfo::in_loop("flag-triplets", "flag-2", "-V, --version"),
+    VariableInfo::in_loop("flag-triplets", "help-2", "Print version"),
+    VariableInfo::in_loop("flag-triplets", "flag-3", "--help"),
+    VariableInfo::in_loop("flag-triplets", "help-3", "Print help"),
+    VariableInfo::sub_loop("positional-lines"),
+    VariableInfo::in_loop("positional-lines", "key", "ROOT"),
+    VariableInfo::in_loop("positional-lines", "help", "Root Directory"),
+    VariableInfo::in_loop("positional-lines", "index", "1"),
+    VariableInfo::in_loop("positional-lines", "id", "root"),
+    VariableInfo::in_loop("positional-lines", "anchor", "opt-root"),
+    VariableInfo::in_loop("positional-lines", "required", " *(required)*"),
+    VariableInfo::in_loop("positional-lines", "details-raw", " *(after `--`)*"),
+    VariableInfo::in_loop("positional-lines", "default", " Default: `.`"),
+    VariableInfo::in_loop(
+        "positional-lines",
+        "gate-note",
+        " *(requires feature `unstable`)*",
+    ),
+    VariableInfo::in_loop(
+        "positional-lines",
+        "possible_values",
+        " Possible values: [`json`, `csv`]",
+    ),
+    VariableInfo::sub_loop("subcommand-lines"),
+    VariableInfo::in_loop("subcommand-lines", "name", "build"),
+    VariableInfo::in_loop("subcommand-lines", "help", "Build the project"),
+    VariableInfo::in_loop("subcommand-lines", "most-used", " *(most used)*"),
+    VariableInfo::in_loop("subcommand-lines", "sub-usage", "add [options] NAME URL"),
+    VariableInfo::sub_loop("subcommand-group-lines"),
+    VariableInfo::in_loop("subcommand-group-lines", "category", "*Build commands*"),
+    VariableInfo::in_loop("subcommand-group-lines", "name", "build"),
+    VariableInfo::in_loop("subcommand-group-lines", "name-styled", "  **build**"),
+    VariableInfo::in_loop("subcommand-group-lines", "help", "Build the project"),
+    VariableInfo::sub_loop("plugin-lines"),
+    VariableInfo::in_loop("plugin-lines", "name", "lint"),
+    VariableInfo::in_loop("plugin-lines", "help", "Lint the sources"),
+    VariableInfo::sub_loop("subcommand-tree-lines"),
+    VariableInfo::in_loop("subcommand-tree-lines", "branch", "│  ├─ "),
+    VariableInfo::in_loop("subcommand-tree-lines", "name", "add"),
+    VariableInfo::in_loop("subcommand-tree-lines", "help", "Add a remote"),
+    VariableInfo::sub_loop("env-lines"),
+    VariableInfo::in_loop("env-lines", "var", "MY_PROG_WIDTH"),
+    VariableInfo::in_loop("env-lines", "flag", "-w, --width"),
+    VariableInfo::in_loop("env-lines", "help", "Width, from there, to there"),
+    VariableInfo::in_loop("env-lines", "value", "120"),
+    VariableInfo::global("changelog-since", "2.3"),
+    VariableInfo::sub_loop("changelog-lines"),
+    VariableInfo::in_loop("changelog-lines", "version", "2.3"),
+    VariableInfo::in_loop("changelog-lines", "name", "--watch"),
+    VariableInfo::in_loop("changelog-lines", "description", "Watch the directory"),
+    VariableInfo::sub_loop("keybinding-lines"),
+    VariableInfo::in_loop("keybinding-lines", "keys", "ctrl-q"),
+    VariableInfo::in_loop("keybinding-lines", "action", "quit"),
+    VariableInfo::in_loop("keybinding-lines", "description", "Quit the application"),
+    VariableInfo::sub_loop("file-lines"),
+    VariableInfo::in_loop("file-lines", "path", "~/.config/broot/conf.hjson"),
+    VariableInfo::in_loop(
+        "file-lines",
+        "expanded-path",
+        "/home/dys/.config/broot/conf.hjson",
+    ),
+    VariableInfo::in_loop("file-lines", "description", "Configuration file"),
+    VariableInfo::sub_loop("glossary-lines"),
+    VariableInfo::in_loop("glossary-lines", "term", "verb"),
+    VariableInfo::in_loop(
+        "glossary-lines",
+        "definition",
+        "A command bound to a key or typed in the input",
+    ),
+    VariableInfo::sub_loop("tips-lines"),
+    VariableInfo::in_loop("tips-lines", "tip", "Short flags may be combined: `-xvf`"),
+    VariableInfo::sub_loop("exit-code-lines"),
+    VariableInfo::in_loop("exit-code-lines", "code", "2"),
+    VariableInfo::in_loop("exit-code-lines", "meaning", "Invalid arguments"),
+    VariableInfo::sub_loop("toc-lines"),
+    VariableInfo::in_loop("toc-lines", "title", "Options"),
+    VariableInfo::in_loop("toc-lines", "anchor", "section-options"),
+    VariableInfo::sub_loop("toc-subcommand-lines"),
+    VariableInfo::in_loop("toc-subcommand-lines", "title", "broot build"),
+    VariableInfo::sub_loop("link-lines"),
+    VariableInfo::in_loop("link-lines", "label", "repository"),
+    VariableInfo::in_loop("link-lines", "url", "https://github.com/Canop/broot"),
+];
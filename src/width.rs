@@ -0,0 +1,110 @@
+use {
+    termimad::{
+        fix_all_tables,
+        minimad::{Alignment, Text},
+        wrap::hard_wrap_lines,
+        CompositeKind, FmtComposite, FmtLine, FmtText, MadSkin, Spacing,
+    },
+    unicode_segmentation::UnicodeSegmentation,
+    unicode_width::{UnicodeWidthChar, UnicodeWidthStr},
+};
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+/// Sum of the widths of the chars, which is how termimad measures texts
+fn chars_width(s: &str) -> usize {
+    s.chars().map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// Return the number of columns a grapheme takes in most terminals
+fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme.contains(EMOJI_PRESENTATION) || grapheme.contains(ZERO_WIDTH_JOINER) {
+        2
+    } else {
+        chars_width(grapheme)
+    }
+}
+
+/// Return the number of columns the string takes in a terminal,
+/// measured by grapheme so that emoji sequences (eg `👨‍👩‍👧` or `⚠️`)
+/// count for their rendered width.
+pub fn str_width(s: &str) -> usize {
+    s.graphemes(true).map(grapheme_width).sum()
+}
+
+/// Lay out a text for the given width like `FmtText::from_text`, but
+/// with the composites measured by grapheme, so that the tables stay
+/// aligned around the emoji sequences whose rendered width isn't the
+/// sum of the widths of their chars, as counted by termimad
+pub(crate) fn fmt_text<'k, 's>(
+    skin: &'k MadSkin,
+    mut text: Text<'s>,
+    width: usize,
+) -> FmtText<'k, 's> {
+    let mut lines: Vec<FmtLine<'s>> = text
+        .lines
+        .drain(..)
+        .map(|line| FmtLine::from(line, skin))
+        .collect();
+    for line in &mut lines {
+        match line {
+            FmtLine::Normal(fc) => remeasure(fc),
+            FmtLine::TableRow(row) => row.cells.iter_mut().for_each(remeasure),
+            _ => {}
+        }
+    }
+    fix_all_tables(&mut lines, width, skin);
+    justify_code_blocks(&mut lines);
+    if width >= 3 {
+        lines = hard_wrap_lines(lines, width, skin).expect("width should be wide enough");
+    }
+    FmtText {
+        skin,
+        lines,
+        width: Some(width),
+    }
+}
+
+/// Correct the visible length of a composite, counted by termimad as
+/// the sum of the widths of its chars, to the width of its graphemes
+fn remeasure(fc: &mut FmtComposite<'_>) {
+    let has_sequence = fc.compounds.iter().any(|c| {
+        c.src
+            .contains(|ch: char| ch == EMOJI_PRESENTATION || ch == ZERO_WIDTH_JOINER)
+    });
+    if !has_sequence {
+        return;
+    }
+    let counted: usize = fc.compounds.iter().map(|c| c.src.width()).sum();
+    let rendered: usize = fc.compounds.iter().map(|c| str_width(c.src)).sum();
+    fc.visible_length = (fc.visible_length + rendered).saturating_sub(counted);
+}
+
+/// Give all the lines of a code block the width of the widest one,
+/// as done by `FmtText::from_text`
+fn justify_code_blocks(lines: &mut [FmtLine<'_>]) {
+    let mut start = 0;
+    while start < lines.len() {
+        let is_code = |line: &FmtLine<'_>| matches!(line, FmtLine::Normal(fc) if fc.kind == CompositeKind::Code);
+        let height = lines[start..]
+            .iter()
+            .take_while(|line| is_code(line))
+            .count();
+        if height == 0 {
+            start += 1;
+            continue;
+        }
+        let block = &mut lines[start..start + height];
+        let width = block.iter().map(FmtLine::visible_length).max().unwrap_or(0);
+        for line in block {
+            if let FmtLine::Normal(fc) = line {
+                fc.spacing = Some(Spacing {
+                    width,
+                    align: Alignment::Left,
+                });
+            }
+        }
+        start += height;
+    }
+}
@@ -0,0 +1,15 @@
+/// How the width of the sections of the help is determined,
+/// see [crate::Printer::with_width_strategy]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WidthStrategy {
+    /// All sections are rendered before being printed, so that they
+    /// all have the width of the widest one
+    #[default]
+    TwoPass,
+    /// Each section is printed as soon as rendered, with its own
+    /// width, so that a huge help isn't held in memory
+    Streaming,
+    /// Each section is printed as soon as rendered, with the whole
+    /// width available for the help
+    Fixed,
+}
@@ -0,0 +1,41 @@
+use {
+    clap::{Arg, ArgAction},
+    std::io::{self, BufRead, Write},
+    termimad::MadSkin,
+};
+
+/// Print the prompt and return the line typed by the user, trimmed,
+/// or `None` when the input is closed
+pub(crate) fn read_answer(skin: &MadSkin, prompt: &str) -> Option<String> {
+    let mut stdout = io::stdout();
+    let _ = write!(stdout, "{} ", skin.inline(prompt));
+    let _ = stdout.flush();
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_string()),
+    }
+}
+
+/// Tell whether the answer to a yes/no question is yes
+pub(crate) fn is_yes(answer: &str) -> bool {
+    matches!(answer.to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Tell whether the argument is the help or version flag generated by clap
+pub(crate) fn is_generated(arg: &Arg) -> bool {
+    matches!(
+        arg.get_action(),
+        ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+    )
+}
+
+/// Quote a value for a POSIX shell, when needed
+pub(crate) fn shell_quote(value: &str) -> String {
+    let is_plain = |c: char| c.is_alphanumeric() || "-_./:=@%+,".contains(c);
+    if !value.is_empty() && value.chars().all(is_plain) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
@@ -0,0 +1,331 @@
+use {
+    crate::*,
+    termimad::{
+        minimad::{Composite, CompositeStyle, Compound, Line, Text},
+        MadSkin,
+    },
+    unicode_segmentation::UnicodeSegmentation,
+};
+
+/// How the continuation lines of a wrapped list item are indented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HangingIndent {
+    /// Aligned with the text following the bullet
+    #[default]
+    Bullet,
+    /// Not indented
+    None,
+    /// Indented by the given number of columns
+    Columns(usize),
+    /// Aligned after the first occurrence of the separator, eg `" : "`
+    /// in [crate::TEMPLATE_OPTIONS_LIST], so that a long description
+    /// is aligned with its first line.
+    ///
+    /// Falls back to [HangingIndent::Bullet] when the separator isn't found
+    AfterSeparator(&'static str),
+}
+
+/// How long texts, like help strings, are wrapped, in table cells
+/// and list items
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct WrapPolicy {
+    pub hanging_indent: HangingIndent,
+    /// Keep the newlines of help strings as line breaks in list items
+    /// and paragraphs, instead of replacing them with spaces.
+    ///
+    /// Table cells can't hold line breaks, so newlines are always
+    /// replaced there.
+    pub preserve_newlines: bool,
+    /// Cut lines at the available width, even in the middle of a word,
+    /// instead of breaking them at spaces
+    pub hard_wrap: bool,
+    /// Add a hyphen where a word is cut, which makes narrow terminals
+    /// more readable.
+    ///
+    /// In table cells, where no hyphen can be added, the long words are
+    /// cut in parts to fill the lines, instead of being moved to the
+    /// next line.
+    pub hyphenate: bool,
+}
+
+/// Used to build the indentation of continuation lines
+const SPACES: &str = "                                ";
+
+/// Width of the parts of the long words of table cells, between
+/// which they may be wrapped
+const WORD_PART_WIDTH: usize = 4;
+
+/// A grapheme of a composite, with its position
+struct Grapheme {
+    compound_idx: usize,
+    start: usize,
+    end: usize,
+    width: usize,
+    is_space: bool,
+    is_newline: bool,
+}
+
+/// Wrap the lines of an expanded text according to the policy.
+///
+/// The lines which don't need anything specific are left untouched,
+/// for termimad to wrap them when it builds the `FmtText`.
+pub(crate) fn apply_wrap_policy(
+    text: &mut Text<'_>,
+    skin: &MadSkin,
+    width: usize,
+    policy: WrapPolicy,
+) {
+    let mut lines = Vec::with_capacity(text.lines.len());
+    for line in text.lines.drain(..) {
+        match line {
+            Line::Normal(mut composite) => {
+                if !policy.preserve_newlines {
+                    replace_newlines(&mut composite);
+                }
+                wrap_composite(composite, skin, width, policy, &mut lines);
+            }
+            Line::TableRow(mut row) => {
+                for cell in &mut row.cells {
+                    replace_newlines(cell);
+                    if policy.hyphenate {
+                        add_soft_breaks(cell, WORD_PART_WIDTH);
+                    }
+                }
+                lines.push(Line::TableRow(row));
+            }
+            line => lines.push(line),
+        }
+    }
+    text.lines = lines;
+}
+
+fn replace_newlines(composite: &mut Composite<'_>) {
+    if !composite.compounds.iter().any(|c| c.src.contains('\n')) {
+        return;
+    }
+    let mut compounds = Vec::with_capacity(composite.compounds.len());
+    for compound in composite.compounds.drain(..) {
+        let mut start = 0;
+        for (idx, _) in compound.src.match_indices('\n') {
+            if idx > start {
+                compounds.push(compound.sub(start, idx));
+            }
+            compounds.push(Compound::raw_str(" "));
+            start = idx + 1;
+        }
+        if start < compound.src.len() {
+            compounds.push(compound.sub(start, compound.src.len()));
+        }
+    }
+    composite.compounds = compounds;
+}
+
+/// Split the compounds inside the words wider than `part_width`, as
+/// termimad may wrap at the limits of compounds: the long words are
+/// then cut in parts instead of being moved to the next line
+fn add_soft_breaks(composite: &mut Composite<'_>, part_width: usize) {
+    if !composite
+        .compounds
+        .iter()
+        .any(|c| c.src.split(' ').any(|word| str_width(word) > part_width))
+    {
+        return;
+    }
+    let mut compounds = Vec::with_capacity(composite.compounds.len());
+    for compound in composite.compounds.drain(..) {
+        let mut part_start = 0;
+        let mut width = 0;
+        for (idx, g) in compound.src.grapheme_indices(true) {
+            if g == " " {
+                width = 0;
+                continue;
+            }
+            if width >= part_width {
+                compounds.push(compound.sub(part_start, idx));
+                part_start = idx;
+                width = 0;
+            }
+            width += str_width(g);
+        }
+        compounds.push(compound.sub(part_start, compound.src.len()));
+    }
+    composite.compounds = compounds;
+}
+
+/// Return the width of the composite up to the end of the first
+/// occurrence of the separator
+fn separator_end(composite: &Composite<'_>, separator: &str) -> Option<usize> {
+    let src: String = composite.compounds.iter().map(|c| c.src).collect();
+    let idx = src.find(separator)?;
+    Some(str_width(&src[..idx + separator.len()]))
+}
+
+fn wrap_composite<'s>(
+    composite: Composite<'s>,
+    skin: &MadSkin,
+    width: usize,
+    policy: WrapPolicy,
+    lines: &mut Vec<Line<'s>>,
+) {
+    let (first_offset, indent) = match composite.style {
+        CompositeStyle::ListItem(depth) => {
+            let bullet = 2 + depth as usize;
+            let indent = match policy.hanging_indent {
+                HangingIndent::Bullet => bullet,
+                HangingIndent::None => 0,
+                HangingIndent::Columns(n) => n,
+                HangingIndent::AfterSeparator(separator) => {
+                    separator_end(&composite, separator).map_or(bullet, |w| bullet + w)
+                }
+            };
+            (bullet, indent)
+        }
+        CompositeStyle::Paragraph => (0, 0),
+        _ => {
+            lines.push(Line::Normal(composite));
+            return;
+        }
+    };
+    let has_newlines = composite.compounds.iter().any(|c| c.src.contains('\n'));
+    let custom_indent = indent != first_offset;
+    if !has_newlines && !custom_indent && !policy.hard_wrap && !policy.hyphenate {
+        lines.push(Line::Normal(composite));
+        return;
+    }
+    let (left_margin, right_margin) = skin
+        .line_style(composite.style.into())
+        .margins_in(Some(width));
+    let width = width.saturating_sub(left_margin + right_margin);
+    let indent = indent.min(width / 2);
+    let mut graphemes = Vec::new();
+    for (compound_idx, compound) in composite.compounds.iter().enumerate() {
+        for (start, g) in compound.src.grapheme_indices(true) {
+            graphemes.push(Grapheme {
+                compound_idx,
+                start,
+                end: start + g.len(),
+                width: str_width(g),
+                is_space: g == " ",
+                is_newline: g == "\n" || g == "\r\n",
+            });
+        }
+    }
+    // ranges of graphemes, one per line, with whether a word is cut at the end
+    let mut rows = Vec::new();
+    let mut row_start = 0;
+    let mut row_width = first_offset;
+    let mut last_space = None;
+    let mut i = 0;
+    while i < graphemes.len() {
+        let g = &graphemes[i];
+        let break_at = if g.is_newline {
+            rows.push((row_start, i, false));
+            Some(i + 1)
+        } else if row_width + g.width > width && i > row_start {
+            match last_space {
+                Some(space) if space > row_start && !policy.hard_wrap => {
+                    rows.push((row_start, space, false));
+                    Some(space + 1)
+                }
+                _ if policy.hyphenate && !g.is_space && !graphemes[i - 1].is_space => {
+                    // the hyphen takes the place of the last grapheme
+                    let end = if i - 1 > row_start { i - 1 } else { i };
+                    rows.push((row_start, end, true));
+                    Some(end)
+                }
+                _ => {
+                    rows.push((row_start, i, false));
+                    Some(i)
+                }
+            }
+        } else {
+            None
+        };
+        if let Some(next) = break_at {
+            row_start = next;
+            while row_start < graphemes.len() && graphemes[row_start].is_space {
+                row_start += 1;
+            }
+            row_width = indent;
+            last_space = None;
+            i = row_start;
+            continue;
+        }
+        if g.is_space {
+            last_space = Some(i);
+        }
+        row_width += g.width;
+        i += 1;
+    }
+    if row_start < graphemes.len() || rows.is_empty() {
+        rows.push((row_start, graphemes.len(), false));
+    }
+    for (row_idx, (start, end, hyphenated)) in rows.into_iter().enumerate() {
+        let mut compounds = Vec::new();
+        if row_idx > 0 {
+            let mut n = indent;
+            while n > 0 {
+                let k = n.min(SPACES.len());
+                compounds.push(Compound::raw_str(&SPACES[..k]));
+                n -= k;
+            }
+        }
+        let mut k = start;
+        while k < end {
+            let compound_idx = graphemes[k].compound_idx;
+            let byte_start = graphemes[k].start;
+            let mut byte_end = graphemes[k].end;
+            while k < end && graphemes[k].compound_idx == compound_idx {
+                byte_end = graphemes[k].end;
+                k += 1;
+            }
+            compounds.push(composite.compounds[compound_idx].sub(byte_start, byte_end));
+        }
+        if hyphenated {
+            compounds.push(Compound::raw_str("-"));
+        }
+        let style = if row_idx == 0 {
+            composite.style
+        } else {
+            CompositeStyle::Paragraph
+        };
+        lines.push(Line::Normal(Composite { style, compounds }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrapped(src: &str, width: usize, policy: WrapPolicy) -> Vec<String> {
+        let mut lines = Vec::new();
+        let composite = Composite::from_inline(src);
+        wrap_composite(composite, &MadSkin::no_style(), width, policy, &mut lines);
+        lines
+            .iter()
+            .map(|line| match line {
+                Line::Normal(composite) => composite.compounds.iter().map(|c| c.src).collect(),
+                _ => panic!("unexpected line {line:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hard_wrap_cuts_words() {
+        let policy = WrapPolicy {
+            hard_wrap: true,
+            ..Default::default()
+        };
+        assert_eq!(wrapped("abcdefghij", 4, policy), ["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn hyphenate_long_words() {
+        let policy = WrapPolicy {
+            hyphenate: true,
+            ..Default::default()
+        };
+        assert_eq!(wrapped("abcdefghij", 4, policy), ["abc-", "def-", "ghij"]);
+        assert_eq!(wrapped("ab cd", 4, policy), ["ab", "cd"]);
+    }
+}